@@ -94,14 +94,15 @@ export const backend = {
   // Processes
   async getProcesses(sort?: string, limit?: number, search?: string) {
     if (isTauri()) {
-      let procs = await tauriCall<any[]>("get_processes");
+      const list = await tauriCall<{ refreshedAt: number; processes: any[] }>("get_processes");
+      let procs = list.processes;
       if (search) {
         const q = search.toLowerCase();
         procs = procs.filter(
           (p: any) =>
             p.command.toLowerCase().includes(q) ||
-            p.args.toLowerCase().includes(q) ||
-            p.path.toLowerCase().includes(q) ||
+            (p.args ?? "").toLowerCase().includes(q) ||
+            (p.path ?? "").toLowerCase().includes(q) ||
             String(p.pid).includes(q)
         );
       }
@@ -117,7 +118,12 @@ export const backend = {
         }
       });
       const sliced = procs.slice(0, limit || 200);
-      return { processes: sliced, total: procs.length, filtered: sliced.length };
+      return {
+        processes: sliced,
+        total: procs.length,
+        filtered: sliced.length,
+        refreshedAt: list.refreshedAt,
+      };
     }
     const params = new URLSearchParams();
     if (sort) params.set("sort", sort);
@@ -173,9 +173,9 @@ export function ProcessesPage() {
       result = result.filter(
         (p) =>
           p.command.toLowerCase().includes(q) ||
-          p.args.toLowerCase().includes(q) ||
-          p.path.toLowerCase().includes(q) ||
-          p.user.toLowerCase().includes(q) ||
+          (p.args ?? "").toLowerCase().includes(q) ||
+          (p.path ?? "").toLowerCase().includes(q) ||
+          (p.user ?? "").toLowerCase().includes(q) ||
           String(p.pid).includes(q)
       );
     }
@@ -519,7 +519,7 @@ export function ProcessesPage() {
                 <InfoCard
                   icon={User}
                   label="User"
-                  value={selectedProcess.user}
+                  value={selectedProcess.user || "N/A"}
                 />
                 <InfoCard
                   icon={Clock}
@@ -29,6 +29,7 @@ const categoryLabels: Record<string, string> = {
   "global-daemons": "Global Daemons",
   "system-agents": "System Agents",
   "system-daemons": "System Daemons",
+  unknown: "Unknown",
 };
 
 const categoryOrder = [
@@ -37,6 +38,7 @@ const categoryOrder = [
   "global-daemons",
   "system-agents",
   "system-daemons",
+  "unknown",
 ];
 
 type OwnerFilter = "all" | "apple" | "third-party";
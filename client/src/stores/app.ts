@@ -83,15 +83,16 @@ export const useServicesStore = create<ServicesStore>((set) => ({
 export interface ProcessInfo {
   pid: number;
   ppid: number;
-  uid: number;
-  user: string;
+  uid?: number;
+  user?: string;
   cpu: number;
   mem: number;
   rss: number;
-  elapsed: string;
+  elapsed?: string;
   command: string;
-  path: string;
-  args: string;
+  // Summary rows omit these; only detailed collection fills them in
+  path?: string;
+  args?: string;
 }
 
 interface ProcessesStore {
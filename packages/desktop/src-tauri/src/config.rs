@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Favorites {
+    pub services: Vec<String>, // labels
+    pub processes: Vec<String>, // command names
+}
+
+// Persisted user settings. Unknown/missing fields fall back to defaults so
+// older config files keep loading as fields are added.
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase", default)]
+pub struct Config {
+    pub favorites: Favorites,
+    pub stats_refresh_interval_ms: u64,
+    pub editor: Option<String>, // app for `open -a`; None uses the default text editor
+    pub flapping_threshold: u32, // restarts per minute before a service counts as flapping
+    pub log_filters: BTreeMap<String, String>, // name -> `log` predicate
+    pub extra_service_dirs: Vec<String>, // scanned for plists alongside the standard launchd dirs
+    pub min_command_interval_ms: u64, // repeat calls to the expensive commands within this get the cached result
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            favorites: Favorites::default(),
+            stats_refresh_interval_ms: 500,
+            editor: None,
+            flapping_threshold: 3,
+            log_filters: BTreeMap::new(),
+            extra_service_dirs: Vec::new(),
+            min_command_interval_ms: 250,
+        }
+    }
+}
+
+static CONFIG: std::sync::LazyLock<Mutex<Config>> =
+    std::sync::LazyLock::new(|| Mutex::new(load()));
+
+// Same directory Tauri uses for app config: ~/Library/Application Support/<identifier>
+fn config_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_default()
+        .join("com.talhaorak.macdash")
+        .join("config.json")
+}
+
+fn load() -> Config {
+    std::fs::read_to_string(config_path())
+        .ok()
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+fn save(config: &Config) -> AppResult<()> {
+    let path = config_path();
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let json = serde_json::to_string_pretty(config)
+        .map_err(|e| AppError::Parse(e.to_string()))?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+pub fn get() -> Config {
+    CONFIG.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+// Applies `f` to the config and persists it; the in-memory copy only changes
+// if both `f` and the write succeed
+pub fn update<F: FnOnce(&mut Config) -> AppResult<()>>(f: F) -> AppResult<Config> {
+    let mut guard = CONFIG.lock().unwrap_or_else(|e| e.into_inner());
+    let mut next = guard.clone();
+    f(&mut next)?;
+    save(&next)?;
+    *guard = next.clone();
+    Ok(next)
+}
+
+fn favorites_list<'a>(favorites: &'a mut Favorites, kind: &str) -> AppResult<&'a mut Vec<String>> {
+    match kind {
+        "service" => Ok(&mut favorites.services),
+        "process" => Ok(&mut favorites.processes),
+        _ => Err(AppError::InvalidArgument(format!("Unknown favorite kind: {}", kind))),
+    }
+}
+
+pub fn add_favorite(kind: &str, name: &str) -> AppResult<Favorites> {
+    let config = update(|c| {
+        let list = favorites_list(&mut c.favorites, kind)?;
+        if !list.iter().any(|n| n == name) {
+            list.push(name.to_string());
+            list.sort();
+        }
+        Ok(())
+    })?;
+    Ok(config.favorites)
+}
+
+pub fn remove_favorite(kind: &str, name: &str) -> AppResult<Favorites> {
+    let config = update(|c| {
+        favorites_list(&mut c.favorites, kind)?.retain(|n| n != name);
+        Ok(())
+    })?;
+    Ok(config.favorites)
+}
+
+pub fn save_log_filter(name: &str, predicate: &str) -> AppResult<BTreeMap<String, String>> {
+    let (name, predicate) = (name.trim(), predicate.trim());
+    if name.is_empty() || predicate.is_empty() {
+        return Err(AppError::InvalidArgument("Filter name and predicate are required".into()));
+    }
+    let config = update(|c| {
+        c.log_filters.insert(name.to_string(), predicate.to_string());
+        Ok(())
+    })?;
+    Ok(config.log_filters)
+}
+
+pub fn delete_log_filter(name: &str) -> AppResult<BTreeMap<String, String>> {
+    let config = update(|c| {
+        c.log_filters.remove(name);
+        Ok(())
+    })?;
+    Ok(config.log_filters)
+}
+
+pub fn log_filter(name: &str) -> AppResult<String> {
+    get().log_filters.get(name)
+        .cloned()
+        .ok_or_else(|| AppError::NotFound(format!("No saved log filter named {}", name)))
+}
@@ -0,0 +1,262 @@
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::error::{AppError, AppResult};
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CheckResult {
+    pub name: String,
+    pub ok: bool,
+    pub detail: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ResourceUsage {
+    pub pid: u32,
+    pub cpu: f32, // percent of one core since the previous call
+    pub rss: u64, // bytes
+    pub thread_count: Option<u32>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SelfUsage {
+    pub app: Option<ResourceUsage>,
+    pub log_stream: Option<ResourceUsage>, // the `log stream` child, while it runs
+}
+
+// CPU time (ns) per pid at the previous call, for turning totals into a rate
+static LAST_CPU: std::sync::LazyLock<std::sync::Mutex<HashMap<u32, (u64, std::time::Instant)>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+fn resource_usage(pid: u32) -> Option<ResourceUsage> {
+    let info = crate::processes::rusage(pid)?;
+    let cpu_ns = crate::processes::mach_time_to_ns(info.ri_user_time + info.ri_system_time);
+
+    let mut last = LAST_CPU.lock().unwrap_or_else(|e| e.into_inner());
+    let cpu = match last.get(&pid) {
+        Some((prev_ns, at)) if at.elapsed().as_nanos() > 0 => {
+            (cpu_ns.saturating_sub(*prev_ns) as f64 / at.elapsed().as_nanos() as f64 * 100.0) as f32
+        }
+        _ => 0.0, // first sample; nothing to diff against yet
+    };
+    last.insert(pid, (cpu_ns, std::time::Instant::now()));
+
+    Some(ResourceUsage {
+        pid,
+        cpu: (cpu * 10.0).round() / 10.0,
+        rss: info.ri_resident_size,
+        thread_count: crate::processes::thread_count(pid),
+    })
+}
+
+pub fn get_self_usage() -> SelfUsage {
+    let app = resource_usage(std::process::id());
+    let log_stream = crate::logs::stream_pid().and_then(resource_usage);
+    let mut last = LAST_CPU.lock().unwrap_or_else(|e| e.into_inner());
+    // Log stream restarts get new pids; don't keep stale baselines around
+    last.retain(|pid, _| *pid == std::process::id() || Some(*pid) == log_stream.as_ref().map(|u| u.pid));
+    SelfUsage { app, log_stream }
+}
+
+// CPU stress for exercising the thermal/CPU plumbing; not compiled into release builds
+#[cfg(debug_assertions)]
+const MAX_STRESS_SECS: u64 = 300;
+
+#[cfg(debug_assertions)]
+static STRESS_RUNNING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+#[cfg(debug_assertions)]
+static STRESS_STOP: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+// Spins `threads` busy threads for `seconds` and returns right away. The
+// threads check a deadline and the stop flag on every iteration, and die with
+// the process like any other thread.
+#[cfg(debug_assertions)]
+pub fn run_cpu_stress(seconds: u64, threads: usize) -> AppResult<usize> {
+    if STRESS_RUNNING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return Err(AppError::InvalidArgument("A CPU stress run is already in progress".into()));
+    }
+    STRESS_STOP.store(false, std::sync::atomic::Ordering::SeqCst);
+
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    let threads = threads.clamp(1, cores * 2);
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(seconds.clamp(1, MAX_STRESS_SECS));
+
+    let workers: Vec<_> = (0..threads).map(|_| {
+        std::thread::spawn(move || {
+            let mut x: u64 = 1;
+            while std::time::Instant::now() < deadline
+                && !STRESS_STOP.load(std::sync::atomic::Ordering::Relaxed)
+            {
+                for _ in 0..10_000 {
+                    x = std::hint::black_box(x.wrapping_mul(6364136223846793005).wrapping_add(1));
+                }
+            }
+        })
+    }).collect();
+
+    // Clears the running flag once every worker has finished
+    std::thread::spawn(move || {
+        for worker in workers {
+            let _ = worker.join();
+        }
+        STRESS_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
+    });
+    Ok(threads)
+}
+
+#[cfg(debug_assertions)]
+pub fn stop_cpu_stress() {
+    STRESS_STOP.store(true, std::sync::atomic::Ordering::SeqCst);
+}
+
+fn check(name: &str, ok: bool, detail: String) -> CheckResult {
+    CheckResult { name: name.into(), ok, detail }
+}
+
+fn find_on_path(tool: &str) -> Option<std::path::PathBuf> {
+    // Apps launched from Finder get a minimal PATH, so this is worth checking
+    let path = std::env::var_os("PATH")?;
+    std::env::split_paths(&path)
+        .map(|dir| dir.join(tool))
+        .find(|p| p.is_file())
+}
+
+// Reading the TCC database needs Full Disk Access; EPERM means we don't have it
+fn full_disk_access() -> (bool, String) {
+    let tcc = "/Library/Application Support/com.apple.TCC/TCC.db";
+    match std::fs::File::open(tcc) {
+        Ok(_) => (true, "granted".into()),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            (false, "not granted; some logs and system plists will be unreadable".into())
+        }
+        Err(e) => (false, format!("could not determine: {}", e)),
+    }
+}
+
+pub fn has_full_disk_access() -> bool {
+    full_disk_access().0
+}
+
+// Privacy & Security > Full Disk Access, on both System Settings and the older
+// System Preferences
+const FDA_SETTINGS_URL: &str =
+    "x-apple.systempreferences:com.apple.preference.security?Privacy_AllFiles";
+
+pub fn open_fda_settings() -> AppResult<()> {
+    let output = std::process::Command::new("open").arg(FDA_SETTINGS_URL).output()?;
+    if !output.status.success() {
+        return Err(AppError::from_stderr(&String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+pub fn self_check() -> Vec<CheckResult> {
+    let mut results = Vec::new();
+
+    for tool in ["log", "launchctl", "lsof"] {
+        let found = find_on_path(tool);
+        let detail = match &found {
+            Some(p) => p.to_string_lossy().to_string(),
+            None => "not found on PATH".into(),
+        };
+        results.push(check(&format!("{} on PATH", tool), found.is_some(), detail));
+    }
+
+    let streaming = crate::logs::is_stream_running();
+    results.push(check(
+        "log stream",
+        streaming,
+        if streaming { "running".into() } else { "not running".into() },
+    ));
+
+    let thermal = crate::system_info::thermal_state();
+    results.push(check(
+        "thermal sensor",
+        thermal != "unknown",
+        format!("thermal state: {}", thermal),
+    ));
+
+    let (fda, detail) = full_disk_access();
+    results.push(check("full disk access", fda, detail));
+
+    results
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TimeSyncStatus {
+    pub enabled: Option<bool>, // None when systemsetup won't answer without admin rights
+    pub server: String, // from /etc/ntp.conf, else Apple's default
+    pub offset_ms: Option<f64>, // positive when the local clock is behind the server
+    pub drifting: bool,         // offset past DRIFT_WARNING_MS
+}
+
+// Enough to break TLS handshakes and make build timestamps lie
+const DRIFT_WARNING_MS: f64 = 1000.0;
+
+// sntp gives up on its own, but a dead network shouldn't hold the command longer than this
+const SNTP_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+async fn command_stdout(args: &[&str]) -> Option<String> {
+    let output = tokio::process::Command::new(args[0])
+        .args(&args[1..])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(SNTP_TIMEOUT, output).await.ok()?.ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// timed reads its server from /etc/ntp.conf: `server time.apple.com`
+fn configured_time_server() -> Option<String> {
+    std::fs::read_to_string("/etc/ntp.conf").ok()?
+        .lines()
+        .find_map(|l| l.trim().strip_prefix("server "))
+        .map(|s| s.split_whitespace().next().unwrap_or_default().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+// `sntp` without -S only queries: "+0.004131 +/- 0.015662 time.apple.com 17.253.4.125"
+fn parse_sntp_offset(output: &str) -> Option<f64> {
+    let seconds: f64 = output.lines().last()?.split_whitespace().next()?.parse().ok()?;
+    Some((seconds * 1000.0 * 10.0).round() / 10.0)
+}
+
+pub async fn get_time_sync_status() -> TimeSyncStatus {
+    let enabled = command_stdout(&["systemsetup", "-getusingnetworktime"]).await
+        .and_then(|out| match out.rsplit(':').next()?.trim() {
+            "On" => Some(true),
+            "Off" => Some(false),
+            _ => None,
+        });
+    let server = configured_time_server().unwrap_or_else(|| "time.apple.com".into());
+    let offset_ms = command_stdout(&["sntp", "-t", "3", &server]).await
+        .as_deref()
+        .and_then(parse_sntp_offset);
+
+    TimeSyncStatus {
+        enabled,
+        drifting: offset_ms.map(|o| o.abs() > DRIFT_WARNING_MS).unwrap_or(false),
+        server,
+        offset_ms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_sntp_offset_in_milliseconds() {
+        assert_eq!(parse_sntp_offset("+0.004131 +/- 0.015662 time.apple.com 17.253.4.125\n"), Some(4.1));
+        assert_eq!(parse_sntp_offset("-0.123456 +/- 0.020000 time.euro.apple.com 17.253.52.253"), Some(-123.5));
+    }
+
+    #[test]
+    fn sntp_failure_has_no_offset() {
+        assert_eq!(parse_sntp_offset(""), None);
+        assert_eq!(parse_sntp_offset("sntp: Exchange failed: Timeout"), None);
+    }
+}
@@ -0,0 +1,81 @@
+use serde::Serialize;
+use std::fmt;
+
+// Errors surfaced to the frontend. `code` is stable so the UI can branch on it
+// (e.g. prompt for a password on "permission-denied") instead of matching text.
+#[derive(Debug, Clone)]
+pub enum AppError {
+    NotFound(String),
+    PermissionDenied(String),
+    InvalidArgument(String),
+    CommandFailed(String),
+    Parse(String),
+    Io(String),
+}
+
+pub type AppResult<T> = Result<T, AppError>;
+
+impl AppError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::NotFound(_) => "not-found",
+            AppError::PermissionDenied(_) => "permission-denied",
+            AppError::InvalidArgument(_) => "invalid-argument",
+            AppError::CommandFailed(_) => "command-failed",
+            AppError::Parse(_) => "parse",
+            AppError::Io(_) => "io",
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::NotFound(m)
+            | AppError::PermissionDenied(m)
+            | AppError::InvalidArgument(m)
+            | AppError::CommandFailed(m)
+            | AppError::Parse(m)
+            | AppError::Io(m) => m,
+        }
+    }
+
+    // Classifies the stderr of a failed command-line tool (kill, launchctl, ...)
+    pub fn from_stderr(stderr: &str) -> AppError {
+        let msg = stderr.trim().to_string();
+        let lower = msg.to_lowercase();
+        if lower.contains("operation not permitted") || lower.contains("permission denied") {
+            AppError::PermissionDenied(msg)
+        } else if lower.contains("no such process") || lower.contains("could not find") {
+            AppError::NotFound(msg)
+        } else {
+            AppError::CommandFailed(msg)
+        }
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for AppError {}
+
+impl From<std::io::Error> for AppError {
+    fn from(e: std::io::Error) -> Self {
+        match e.kind() {
+            std::io::ErrorKind::NotFound => AppError::NotFound(e.to_string()),
+            std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(e.to_string()),
+            _ => AppError::Io(e.to_string()),
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut s = serializer.serialize_struct("AppError", 2)?;
+        s.serialize_field("code", self.code())?;
+        s.serialize_field("message", self.message())?;
+        s.end()
+    }
+}
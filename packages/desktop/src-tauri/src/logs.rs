@@ -1,7 +1,14 @@
 use serde::Serialize;
-use std::sync::Mutex;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::{Mutex, RwLock};
+use tauri::Emitter;
 use tokio::process::Command;
 use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::oneshot;
+
+use crate::error::{AppError, AppResult};
+use crate::timings::{self, Stage};
 
 #[derive(Serialize, Clone, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -13,16 +20,76 @@ pub struct LogEntry {
     pub message: String,
     pub subsystem: Option<String>,
     pub category: Option<String>,
+    pub seq: Option<u64>, // position in the live stream buffer; None for `log show` results
+}
+
+// Ring of the newest MAX_BUFFER streamed entries, oldest first. Readers share
+// the lock so get_recent_logs doesn't stall the stream during bursts.
+static LOG_BUFFER: std::sync::LazyLock<RwLock<VecDeque<LogEntry>>> =
+    std::sync::LazyLock::new(|| RwLock::new(VecDeque::with_capacity(MAX_BUFFER)));
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStreamStats {
+    pub running: bool,
+    pub buffered: usize,
+    pub predicate: Option<String>,
+    pub latest_timestamp: Option<String>,
+    pub lag_seconds: Option<f64>, // now minus the newest entry; keeps growing if the stream stalls
+    pub last_error: Option<LogStreamError>, // from the current stream's stderr; survives until the next restart
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogStreamError {
+    pub kind: String, // "permission-denied" | "other"
+    pub message: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowedLogEntry {
+    pub follow: String,
+    pub entry: LogEntry,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FollowError {
+    pub follow: String,
+    pub error: LogStreamError,
 }
 
-static LOG_BUFFER: std::sync::LazyLock<Mutex<Vec<LogEntry>>> =
-    std::sync::LazyLock::new(|| Mutex::new(Vec::new()));
+// Per-process follow streams, keyed by process name. Dropping/sending on the
+// sender stops that follow's `log stream` child.
+static FOLLOWS: std::sync::LazyLock<Mutex<HashMap<String, oneshot::Sender<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub const FOLLOW_EVENT: &str = "log-follow";
+pub const FOLLOW_ERROR_EVENT: &str = "log-follow-error";
+pub const STREAM_ERROR_EVENT: &str = "log-stream-error";
+
+// Set once at startup so stream restarts from any command can emit errors
+static APP: std::sync::OnceLock<tauri::AppHandle> = std::sync::OnceLock::new();
+
+static STREAM_ERROR: std::sync::LazyLock<Mutex<Option<LogStreamError>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
 
 static STREAM_RUNNING: std::sync::atomic::AtomicBool =
     std::sync::atomic::AtomicBool::new(false);
 
 const MAX_BUFFER: usize = 1000;
 
+// seq of the newest buffered entry; 0 before the first one
+static LAST_SEQ: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogsSince {
+    pub entries: Vec<LogEntry>,
+    pub cursor: u64, // pass back as `cursor` on the next poll
+}
+
 fn parse_log_level(level: &str) -> &'static str {
     let l = level.to_lowercase();
     if l.contains("error") || l.contains("fault") { "error" }
@@ -47,6 +114,7 @@ fn parse_compact_line(line: &str) -> Option<LogEntry> {
                 message: val["eventMessage"].as_str().or(val["message"].as_str()).unwrap_or("").to_string(),
                 subsystem: val["subsystem"].as_str().map(|s| s.to_string()),
                 category: val["category"].as_str().map(|s| s.to_string()),
+                seq: None,
             });
         }
     }
@@ -79,6 +147,7 @@ fn parse_compact_line(line: &str) -> Option<LogEntry> {
                         message,
                         subsystem: None,
                         category: None,
+                        seq: None,
                     });
                 }
             }
@@ -94,96 +163,588 @@ fn parse_compact_line(line: &str) -> Option<LogEntry> {
         message: line.to_string(),
         subsystem: None,
         category: None,
+        seq: None,
     })
 }
 
-pub fn start_log_stream() {
-    if STREAM_RUNNING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+// Predicate the main stream runs with; None streams everything
+static STREAM_PREDICATE: std::sync::LazyLock<Mutex<Option<String>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+// Stops the current stream child so a replacement can take over; the
+// replacement owns STREAM_RUNNING from then on
+static STREAM_RESTART: std::sync::LazyLock<Mutex<Option<oneshot::Sender<()>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+// Pid of the running `log stream` child; 0 when there isn't one
+static STREAM_PID: std::sync::atomic::AtomicU32 = std::sync::atomic::AtomicU32::new(0);
+
+pub fn stream_pid() -> Option<u32> {
+    Some(STREAM_PID.load(std::sync::atomic::Ordering::SeqCst)).filter(|p| *p != 0)
+}
+
+pub fn start_log_stream(app: tauri::AppHandle, predicate: Option<String>) {
+    let _ = APP.set(app);
+    let changed = predicate.is_some() && set_predicate(predicate);
+    if STREAM_RUNNING.swap(true, std::sync::atomic::Ordering::SeqCst) && !changed {
         return; // already running
     }
+    spawn_stream();
+}
+
+// Rescopes the live stream, restarting `log stream` if it's running.
+// Returns whether the predicate changed.
+pub fn set_stream_predicate(predicate: Option<String>) -> bool {
+    let changed = set_predicate(predicate);
+    if changed && is_stream_running() {
+        spawn_stream();
+    }
+    changed
+}
+
+const ERRORS_ONLY_PREDICATE: &str = "messageType == error OR messageType == fault";
+
+// Predicate in effect before errors-only was switched on, restored when it's
+// switched off; None while errors-only is off
+static PRE_ERRORS_ONLY: std::sync::LazyLock<Mutex<Option<Option<String>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+// Toggles the live stream between errors/faults and whatever it showed
+// before. The buffer is kept across the switch.
+pub fn set_errors_only(enabled: bool) -> bool {
+    let mut saved = PRE_ERRORS_ONLY.lock().unwrap_or_else(|e| e.into_inner());
+    if enabled == saved.is_some() { return false; }
+    let next = if enabled {
+        *saved = Some(STREAM_PREDICATE.lock().unwrap_or_else(|e| e.into_inner()).clone());
+        Some(ERRORS_ONLY_PREDICATE.to_string())
+    } else {
+        saved.take().flatten()
+    };
+    drop(saved);
+    set_stream_predicate(next)
+}
+
+fn set_predicate(predicate: Option<String>) -> bool {
+    let predicate = predicate.filter(|p| !p.trim().is_empty());
+    let mut current = STREAM_PREDICATE.lock().unwrap_or_else(|e| e.into_inner());
+    if *current == predicate { return false; }
+    *current = predicate;
+    true
+}
+
+// `log stream` says why it can't stream on stderr and then exits or sits
+// silent, which otherwise looks exactly like a quiet system
+fn classify_stream_error(message: &str) -> LogStreamError {
+    let lower = message.to_lowercase();
+    let denied = ["not permitted", "permission", "entitlement", "must be run as root", "not authorized"]
+        .iter()
+        .any(|needle| lower.contains(needle));
+    LogStreamError {
+        kind: if denied { "permission-denied" } else { "other" }.into(),
+        message: message.trim().to_string(),
+    }
+}
+
+fn report_stream_error(message: &str) {
+    let error = classify_stream_error(message);
+    if let Some(app) = APP.get() {
+        let _ = app.emit(STREAM_ERROR_EVENT, &error);
+    }
+    *STREAM_ERROR.lock().unwrap_or_else(|e| e.into_inner()) = Some(error);
+}
+
+fn spawn_stream() {
+    let (restart_tx, mut restart_rx) = oneshot::channel();
+    if let Some(previous) = STREAM_RESTART.lock().unwrap_or_else(|e| e.into_inner()).replace(restart_tx) {
+        let _ = previous.send(());
+    }
+    let predicate = STREAM_PREDICATE.lock().unwrap_or_else(|e| e.into_inner()).clone();
 
-    tauri::async_runtime::spawn(async {
+    tauri::async_runtime::spawn(async move {
+        let mut args = vec!["stream", "--style", "compact", "--level", "info"];
+        if let Some(pred) = &predicate {
+            args.extend(["--predicate", pred.as_str()]);
+        }
         let mut child = match Command::new("log")
-            .args(["stream", "--style", "compact", "--level", "info"])
+            .args(&args)
             .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
             .spawn()
         {
             Ok(c) => c,
-            Err(_) => {
+            Err(e) => {
+                report_stream_error(&e.to_string());
                 STREAM_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
                 return;
             }
         };
+        *STREAM_ERROR.lock().unwrap_or_else(|e| e.into_inner()) = None;
+
+        if let Some(stderr) = child.stderr.take() {
+            tauri::async_runtime::spawn(async move {
+                let mut lines = BufReader::new(stderr).lines();
+                while let Ok(Some(line)) = lines.next_line().await {
+                    if !line.trim().is_empty() {
+                        report_stream_error(&line);
+                    }
+                }
+            });
+        }
 
+        if let Some(pid) = child.id() {
+            STREAM_PID.store(pid, std::sync::atomic::Ordering::SeqCst);
+        }
         let stdout = child.stdout.take().unwrap();
         let mut reader = BufReader::new(stdout).lines();
+        let mut replaced = false;
 
-        while let Ok(Some(line)) = reader.next_line().await {
-            if !STREAM_RUNNING.load(std::sync::atomic::Ordering::SeqCst) {
-                break;
-            }
-            if let Some(entry) = parse_compact_line(&line) {
-                let mut buf = LOG_BUFFER.lock().unwrap();
-                buf.push(entry);
-                if buf.len() > MAX_BUFFER {
-                    let drain = buf.len() - MAX_BUFFER;
-                    buf.drain(..drain);
+        loop {
+            tokio::select! {
+                line = reader.next_line() => {
+                    let Ok(Some(line)) = line else { break };
+                    if !STREAM_RUNNING.load(std::sync::atomic::Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Some(mut entry) = parse_compact_line(&line) {
+                        let mut buf = LOG_BUFFER.write().unwrap_or_else(|e| e.into_inner());
+                        if buf.len() >= MAX_BUFFER {
+                            buf.pop_front();
+                        }
+                        // Assigned under the write lock so seq order matches buffer order
+                        entry.seq = Some(LAST_SEQ.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1);
+                        buf.push_back(entry);
+                    }
+                }
+                _ = &mut restart_rx => {
+                    replaced = true;
+                    break;
                 }
             }
         }
 
         let _ = child.kill().await;
-        STREAM_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
+        if !replaced {
+            STREAM_PID.store(0, std::sync::atomic::Ordering::SeqCst);
+            STREAM_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
+        }
     });
 }
 
+pub fn is_stream_running() -> bool {
+    STREAM_RUNNING.load(std::sync::atomic::Ordering::SeqCst)
+}
+
+// How far `now` is past an entry's timestamp, clamped at zero
+fn seconds_behind(ts: &str, now: chrono::DateTime<chrono::FixedOffset>) -> Option<f64> {
+    parse_timestamp(ts).map(|t| (now - t).num_milliseconds().max(0) as f64 / 1000.0)
+}
+
+pub fn get_log_stream_stats() -> LogStreamStats {
+    let (buffered, latest_timestamp) = {
+        let buf = LOG_BUFFER.read().unwrap_or_else(|e| e.into_inner());
+        (buf.len(), buf.back().map(|e| e.timestamp.clone()))
+    };
+    let lag_seconds = latest_timestamp.as_deref()
+        .and_then(|ts| seconds_behind(ts, chrono::Local::now().fixed_offset()));
+
+    LogStreamStats {
+        running: is_stream_running(),
+        buffered,
+        predicate: STREAM_PREDICATE.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+        latest_timestamp,
+        lag_seconds,
+        last_error: STREAM_ERROR.lock().unwrap_or_else(|e| e.into_inner()).clone(),
+    }
+}
+
 pub fn stop_log_stream() {
     STREAM_RUNNING.store(false, std::sync::atomic::Ordering::SeqCst);
 }
 
-pub fn get_recent_logs(count: usize) -> Vec<LogEntry> {
-    let buf = LOG_BUFFER.lock().unwrap();
-    let start = buf.len().saturating_sub(count);
-    buf[start..].to_vec()
+// Fails only if `log` can't be started; a rejected predicate or missing
+// permission shows up afterwards as FOLLOW_ERROR_EVENT, like the main stream's
+// STREAM_ERROR_EVENT
+pub async fn follow_process_logs(app: tauri::AppHandle, process_name: String) -> AppResult<()> {
+    let (stop_tx, mut stop_rx) = oneshot::channel();
+    {
+        let mut follows = FOLLOWS.lock().unwrap_or_else(|e| e.into_inner());
+        if follows.contains_key(&process_name) {
+            return Ok(()); // already following
+        }
+        follows.insert(process_name.clone(), stop_tx);
+    }
+
+    let escaped = process_name.replace('\\', "\\\\").replace('"', "\\\"");
+    let predicate = format!("process == \"{}\"", escaped);
+    let mut child = match Command::new("log")
+        .args(["stream", "--style", "compact", "--level", "info", "--predicate", &predicate])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(&process_name);
+            return Err(AppError::CommandFailed(format!("Couldn't start log stream: {}", e)));
+        }
+    };
+
+    if let Some(stderr) = child.stderr.take() {
+        let (app, follow) = (app.clone(), process_name.clone());
+        tauri::async_runtime::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if !line.trim().is_empty() {
+                    let _ = app.emit(FOLLOW_ERROR_EVENT, FollowError {
+                        follow: follow.clone(),
+                        error: classify_stream_error(&line),
+                    });
+                }
+            }
+        });
+    }
+
+    tauri::async_runtime::spawn(async move {
+        let stdout = child.stdout.take().unwrap();
+        let mut reader = BufReader::new(stdout).lines();
+        let mut stopped = false;
+
+        loop {
+            tokio::select! {
+                line = reader.next_line() => {
+                    let Ok(Some(line)) = line else { break };
+                    if let Some(entry) = parse_compact_line(&line) {
+                        let _ = app.emit(FOLLOW_EVENT, FollowedLogEntry {
+                            follow: process_name.clone(),
+                            entry,
+                        });
+                    }
+                }
+                _ = &mut stop_rx => {
+                    stopped = true;
+                    break;
+                }
+            }
+        }
+
+        let _ = child.kill().await;
+        // An unfollow already removed our entry (and a re-follow may have replaced it)
+        if !stopped {
+            FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(&process_name);
+        }
+    });
+    Ok(())
+}
+
+pub fn unfollow_process_logs(process_name: &str) -> bool {
+    let stop = FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(process_name);
+    match stop {
+        Some(tx) => {
+            let _ = tx.send(());
+            true
+        }
+        None => false,
+    }
+}
+
+// Severity order of the levels parse_log_level produces
+fn level_rank(level: &str) -> Option<u8> {
+    match level {
+        "debug" => Some(0),
+        "info" => Some(1),
+        "default" => Some(2),
+        "warning" => Some(3),
+        "error" => Some(4),
+        _ => None,
+    }
+}
+
+// Newest `count` entries, optionally only those at or above `min_level`
+pub fn get_recent_logs(count: usize, min_level: Option<&str>) -> AppResult<Vec<LogEntry>> {
+    let buf = LOG_BUFFER.read().unwrap_or_else(|e| e.into_inner());
+    let Some(level) = min_level else {
+        let start = buf.len().saturating_sub(count);
+        return Ok(buf.range(start..).cloned().collect());
+    };
+    let min = level_rank(level)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown log level: {}", level)))?;
+
+    let mut entries: Vec<LogEntry> = buf.iter()
+        .rev()
+        .filter(|e| level_rank(&e.level).map(|r| r >= min).unwrap_or(false))
+        .take(count)
+        .cloned()
+        .collect();
+    entries.reverse();
+    Ok(entries)
+}
+
+// Buffered entries newer than `cursor`. Entries evicted before the caller
+// caught up are simply gone; the returned cursor still moves past them.
+pub fn get_logs_since(cursor: u64) -> LogsSince {
+    let buf = LOG_BUFFER.read().unwrap_or_else(|e| e.into_inner());
+    // A cursor from before an app restart is ahead of us; start over
+    let cursor = if cursor > LAST_SEQ.load(std::sync::atomic::Ordering::SeqCst) { 0 } else { cursor };
+    let start = buf.partition_point(|e| e.seq.unwrap_or(0) <= cursor);
+    LogsSince {
+        entries: buf.range(start..).cloned().collect(),
+        cursor: buf.back().and_then(|e| e.seq).unwrap_or(cursor),
+    }
+}
+
+// Newest `count` buffered entries from any of `names` (exact match), in
+// chronological order
+pub fn get_recent_logs_for_processes(names: &[String], count: usize) -> Vec<LogEntry> {
+    let buf = LOG_BUFFER.read().unwrap_or_else(|e| e.into_inner());
+    let mut entries: Vec<LogEntry> = buf.iter()
+        .rev()
+        .filter(|e| names.contains(&e.process))
+        .take(count)
+        .cloned()
+        .collect();
+    entries.reverse();
+    entries
 }
 
-pub async fn query_logs(last_minutes: u32, predicate: Option<&str>) -> Vec<LogEntry> {
-    let mut args = vec![
-        "log".to_string(), "show".to_string(),
-        "--last".to_string(), format!("{}m", last_minutes),
-        "--style".to_string(), "compact".to_string(),
-    ];
+// Runs `log show` over `range` (--last or --start/--end) and parses every line
+async fn log_show(range: &[String], predicate: Option<&str>) -> Option<Vec<LogEntry>> {
+    let started = std::time::Instant::now();
+    let mut args = vec!["show".to_string()];
+    args.extend_from_slice(range);
+    args.extend(["--style".to_string(), "compact".to_string()]);
     if let Some(pred) = predicate {
         args.push("--predicate".to_string());
         args.push(pred.to_string());
     }
 
-    let output = Command::new(&args[0])
-        .args(&args[1..])
-        .output()
-        .await;
+    let output = Command::new("log").args(&args).kill_on_drop(true).output().await;
+    timings::record(Stage::LogQuery, started);
+    let output = output.ok()?;
+    Some(String::from_utf8_lossy(&output.stdout).lines().filter_map(parse_compact_line).collect())
+}
+
+// Parsed `log show` results by (minutes, predicate), so paging through one
+// window doesn't re-run the subprocess for every page
+type QueryKey = (u32, Option<String>);
+
+static QUERY_CACHE: std::sync::LazyLock<Mutex<HashMap<QueryKey, (std::time::Instant, std::sync::Arc<Vec<LogEntry>>)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+const QUERY_CACHE_TTL: std::time::Duration = std::time::Duration::from_secs(30);
+
+pub const DEFAULT_QUERY_LIMIT: usize = 500;
+
+async fn run_log_show(last_minutes: u32, predicate: Option<&str>) -> std::sync::Arc<Vec<LogEntry>> {
+    let key = (last_minutes, predicate.map(|p| p.to_string()));
+    {
+        let mut cache = QUERY_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+        cache.retain(|_, (at, _)| at.elapsed() < QUERY_CACHE_TTL);
+        if let Some((_, entries)) = cache.get(&key) {
+            return entries.clone();
+        }
+    }
+
+    // Don't cache failures; the next page should retry
+    let Some(entries) = log_show(&["--last".to_string(), format!("{}m", last_minutes)], predicate).await else {
+        return std::sync::Arc::new(Vec::new());
+    };
+    let entries = std::sync::Arc::new(entries);
+    QUERY_CACHE.lock().unwrap_or_else(|e| e.into_inner())
+        .insert(key, (std::time::Instant::now(), entries.clone()));
+    entries
+}
+
+// Pages count back from the newest entry: offset 0 is the most recent
+// `limit` entries. Each page is returned in chronological order.
+pub async fn query_logs(last_minutes: u32, predicate: Option<&str>, limit: usize, offset: usize) -> Vec<LogEntry> {
+    let entries = run_log_show(last_minutes, predicate).await;
+    let end = entries.len().saturating_sub(offset);
+    let start = end.saturating_sub(limit);
+    entries[start..end].to_vec()
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogPage {
+    pub entries: Vec<LogEntry>,      // chronological
+    pub next_cursor: Option<String>, // "<oldest timestamp>#<entries returned at it>"; None once the history is exhausted
+}
+
+// First window looked at before a cursor, grown eightfold until a page fills
+// up; 5 minutes reaches the 7 day cap in at most five `log show` runs
+const PAGE_WINDOW_START: chrono::Duration = chrono::Duration::minutes(5);
+const PAGE_WINDOW_MAX: chrono::Duration = chrono::Duration::days(7);
+const PAGE_WINDOW_GROWTH: i32 = 8;
+const PAGE_RUN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+// The compact style prints local time without an offset, e.g. "2024-01-01 12:00:00.123";
+// ndjson adds one, e.g. "2024-01-01 12:00:00.123456+0300"
+const COMPACT_TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S%.f";
+const OFFSET_TIMESTAMP: &str = "%Y-%m-%d %H:%M:%S%.f%z";
+
+fn parse_timestamp(ts: &str) -> Option<chrono::DateTime<chrono::FixedOffset>> {
+    chrono::DateTime::parse_from_str(ts, OFFSET_TIMESTAMP).ok()
+        .or_else(|| {
+            chrono::NaiveDateTime::parse_from_str(ts, COMPACT_TIMESTAMP).ok()?
+                .and_local_timezone(chrono::Local)
+                .earliest()
+                .map(|t| t.fixed_offset())
+        })
+        .or_else(|| chrono::DateTime::parse_from_rfc3339(ts).ok())
+}
+
+// Where the next page ends. Several entries can share a timestamp, so the
+// cursor also counts how many at `end` earlier pages returned (the newest
+// ones); the rest still belong to the next page. A bare timestamp, with no
+// count, excludes everything at `end`.
+struct PageCursor {
+    end: chrono::DateTime<chrono::FixedOffset>,
+    returned: Option<usize>,
+}
+
+impl PageCursor {
+    fn parse(cursor: &str) -> Option<PageCursor> {
+        match cursor.rsplit_once('#') {
+            Some((ts, n)) => Some(PageCursor { end: parse_timestamp(ts)?, returned: Some(n.parse().ok()?) }),
+            None => Some(PageCursor { end: parse_timestamp(cursor)?, returned: None }),
+        }
+    }
+
+    // Drops everything chronological `entries` has at or past the cursor
+    fn apply(&self, entries: &mut Vec<LogEntry>) {
+        entries.retain(|e| parse_timestamp(&e.timestamp).map(|t| t <= self.end).unwrap_or(false));
+        let at_end = entries.iter().rev()
+            .take_while(|e| parse_timestamp(&e.timestamp) == Some(self.end))
+            .count();
+        let returned = self.returned.map_or(at_end, |n| n.min(at_end));
+        entries.truncate(entries.len() - returned);
+    }
+
+    // The cursor past `page`, which was cut from entries this one was applied to
+    fn next(&self, page: &[LogEntry]) -> Option<String> {
+        let oldest = page.first()?;
+        let t = parse_timestamp(&oldest.timestamp)?;
+        let mut returned = page.iter().take_while(|e| parse_timestamp(&e.timestamp) == Some(t)).count();
+        if t == self.end {
+            returned += self.returned.unwrap_or(0);
+        }
+        Some(format!("{}#{}", oldest.timestamp, returned))
+    }
+}
+
+// Entries before `before` (now when None), newest `limit` of them. Pass back
+// `next_cursor` as `before` to load the page above.
+pub async fn query_logs_paged(before: Option<&str>, limit: usize, predicate: Option<&str>) -> AppResult<LogPage> {
+    let cursor = match before {
+        Some(c) => PageCursor::parse(c)
+            .ok_or_else(|| AppError::InvalidArgument(format!("Invalid log cursor: {}", c)))?,
+        None => PageCursor { end: chrono::Local::now().fixed_offset(), returned: None },
+    };
+    let end = cursor.end;
+    // `log show` only takes whole seconds; the bound is enforced after parsing
+    let fmt = |t: chrono::DateTime<chrono::FixedOffset>| t.format("%Y-%m-%d %H:%M:%S%z").to_string();
 
-    match output {
-        Ok(o) => {
-            let text = String::from_utf8_lossy(&o.stdout);
-            text.lines()
-                .filter_map(parse_compact_line)
-                .collect::<Vec<_>>()
-                .into_iter()
-                .rev()
-                .take(500)
-                .collect::<Vec<_>>()
-                .into_iter()
-                .rev()
-                .collect()
+    let mut window = PAGE_WINDOW_START;
+    loop {
+        let start = end - window;
+        let range = ["--start".to_string(), fmt(start), "--end".to_string(), fmt(end + chrono::Duration::seconds(1))];
+        let mut entries = tokio::time::timeout(PAGE_RUN_TIMEOUT, log_show(&range, predicate)).await
+            .map_err(|_| AppError::CommandFailed(format!(
+                "log show timed out after {}s", PAGE_RUN_TIMEOUT.as_secs()
+            )))?
+            .unwrap_or_default();
+        cursor.apply(&mut entries);
+
+        if entries.len() >= limit || window >= PAGE_WINDOW_MAX {
+            let exhausted = entries.len() < limit;
+            entries.drain(..entries.len().saturating_sub(limit));
+            let next_cursor = if exhausted { None } else { cursor.next(&entries) };
+            return Ok(LogPage { entries, next_cursor });
         }
-        Err(_) => Vec::new(),
+        window = (window * PAGE_WINDOW_GROWTH).min(PAGE_WINDOW_MAX);
+    }
+}
+
+pub async fn query_logs_for_pid(pid: u32, last_minutes: u32) -> Vec<LogEntry> {
+    // The process has exited; its old logs belong to history, not the table row
+    if crate::processes::process_name(pid).is_none() {
+        return Vec::new();
+    }
+    query_logs(last_minutes, Some(&format!("processID == {}", pid)), DEFAULT_QUERY_LIMIT, 0).await
+}
+
+// Level each subsystem had before we first changed it, for the reset
+static SUBSYSTEM_LEVELS: std::sync::LazyLock<Mutex<HashMap<String, Option<String>>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+const SUBSYSTEM_LEVEL_NAMES: [&str; 4] = ["off", "default", "info", "debug"];
+
+fn check_subsystem(subsystem: &str) -> AppResult<()> {
+    // It ends up inside a shell command run as root
+    let valid = !subsystem.is_empty()
+        && subsystem.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'));
+    if valid { Ok(()) } else { Err(AppError::InvalidArgument(format!("Invalid subsystem: {}", subsystem))) }
+}
+
+// `log config --status` prints e.g. `Mode for 'com.apple.foo'  INFO PERSIST_DEFAULT`
+async fn subsystem_level(subsystem: &str) -> Option<String> {
+    let output = Command::new("log")
+        .args(["config", "--subsystem", subsystem, "--status"])
+        .output()
+        .await
+        .ok()?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let (_, mode) = stdout.rsplit_once('\'')?;
+    let level = mode.split_whitespace().next()?.to_lowercase();
+    SUBSYSTEM_LEVEL_NAMES.contains(&level.as_str()).then_some(level)
+}
+
+// `log config` needs root, so this goes through the admin password prompt
+async fn run_log_config_as_admin(args: &str) -> AppResult<()> {
+    let script = format!("do shell script \"/usr/bin/log config {}\" with administrator privileges", args);
+    let output = Command::new("osascript").args(["-e", &script]).output().await?;
+    if output.status.success() {
+        return Ok(());
     }
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    // -128 is the user dismissing the password prompt
+    if stderr.contains("(-128)") {
+        return Err(AppError::PermissionDenied("Administrator authorization was cancelled".into()));
+    }
+    Err(AppError::from_stderr(&stderr))
+}
+
+pub async fn set_subsystem_log_level(subsystem: &str, level: &str) -> AppResult<()> {
+    check_subsystem(subsystem)?;
+    let level = level.to_lowercase();
+    if !SUBSYSTEM_LEVEL_NAMES.contains(&level.as_str()) {
+        return Err(AppError::InvalidArgument(format!("Unknown log level: {}", level)));
+    }
+    let original = subsystem_level(subsystem).await;
+    run_log_config_as_admin(&format!("--subsystem {} --mode level:{}", subsystem, level)).await?;
+    // Only the first change records the original; later ones keep it
+    SUBSYSTEM_LEVELS.lock().unwrap_or_else(|e| e.into_inner())
+        .entry(subsystem.to_string())
+        .or_insert(original);
+    Ok(())
+}
+
+pub async fn reset_subsystem_log_level(subsystem: &str) -> AppResult<()> {
+    check_subsystem(subsystem)?;
+    let original = SUBSYSTEM_LEVELS.lock().unwrap_or_else(|e| e.into_inner()).get(subsystem).cloned();
+    let args = match original {
+        Some(Some(level)) => format!("--subsystem {} --mode level:{}", subsystem, level),
+        // Never changed here, or its old level was unreadable: back to the system default
+        _ => format!("--subsystem {} --reset", subsystem),
+    };
+    run_log_config_as_admin(&args).await?;
+    SUBSYSTEM_LEVELS.lock().unwrap_or_else(|e| e.into_inner()).remove(subsystem);
+    Ok(())
 }
 
 pub fn get_active_log_processes() -> Vec<(String, usize, String)> {
-    let buf = LOG_BUFFER.lock().unwrap();
+    let buf = LOG_BUFFER.read().unwrap_or_else(|e| e.into_inner());
     let mut counts: std::collections::HashMap<String, (usize, String)> = std::collections::HashMap::new();
 
     for entry in buf.iter() {
@@ -197,6 +758,107 @@ pub fn get_active_log_processes() -> Vec<(String, usize, String)> {
     let mut result: Vec<_> = counts.into_iter()
         .map(|(name, (count, last_seen))| (name, count, last_seen))
         .collect();
-    result.sort_by(|a, b| b.1.cmp(&a.1));
+    result.sort_by_key(|r| std::cmp::Reverse(r.1));
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_offsetless_compact_timestamp_as_local_time() {
+        let line = "2024-01-15 10:23:45.123 Df kernel[0:1f2] (AppleACPIPlatform) ACPI: sleep states S3 S4 S5";
+        let entry = parse_compact_line(line).unwrap();
+        assert_eq!(entry.timestamp, "2024-01-15 10:23:45.123");
+        assert_eq!(entry.process, "kernel");
+
+        let t = parse_timestamp(&entry.timestamp).unwrap();
+        let expected = chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+            .and_hms_milli_opt(10, 23, 45, 123).unwrap();
+        assert_eq!(t.naive_local(), expected);
+    }
+
+    #[test]
+    fn parses_timestamps_with_offsets() {
+        let t = parse_timestamp("2024-01-15 10:23:45.123456-0800").unwrap();
+        assert_eq!(t.offset().local_minus_utc(), -8 * 3600);
+
+        let t = parse_timestamp("2024-01-15T10:23:45.123+03:00").unwrap();
+        assert_eq!(t.offset().local_minus_utc(), 3 * 3600);
+
+        assert!(parse_timestamp("yesterday").is_none());
+    }
+
+    fn entry(timestamp: &str, message: &str) -> LogEntry {
+        LogEntry {
+            timestamp: timestamp.into(),
+            level: "default".into(),
+            process: "kernel".into(),
+            pid: Some(0),
+            message: message.into(),
+            subsystem: None,
+            category: None,
+            seq: None,
+        }
+    }
+
+    fn messages(entries: &[LogEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.message.as_str()).collect()
+    }
+
+    #[test]
+    fn pages_through_entries_sharing_a_timestamp() {
+        let all = vec![
+            entry("2024-01-15 10:23:45.100", "a"),
+            entry("2024-01-15 10:23:45.200", "b1"),
+            entry("2024-01-15 10:23:45.200", "b2"),
+            entry("2024-01-15 10:23:45.200", "b3"),
+            entry("2024-01-15 10:23:45.300", "c"),
+        ];
+        let page = |cursor: &PageCursor| {
+            let mut entries = all.clone();
+            cursor.apply(&mut entries);
+            entries.drain(..entries.len().saturating_sub(2));
+            let next = cursor.next(&entries);
+            (entries, next)
+        };
+
+        let first = PageCursor::parse("2024-01-15 10:23:46.000").unwrap();
+        let (entries, next) = page(&first);
+        assert_eq!(messages(&entries), ["b3", "c"]);
+        assert_eq!(next.as_deref(), Some("2024-01-15 10:23:45.200#1"));
+
+        let second = PageCursor::parse(&next.unwrap()).unwrap();
+        let (entries, next) = page(&second);
+        assert_eq!(messages(&entries), ["b1", "b2"]);
+        assert_eq!(next.as_deref(), Some("2024-01-15 10:23:45.200#3"));
+
+        let third = PageCursor::parse(&next.unwrap()).unwrap();
+        let (entries, _) = page(&third);
+        assert_eq!(messages(&entries), ["a"]);
+    }
+
+    #[test]
+    fn bare_timestamp_cursor_excludes_its_own_entries() {
+        let mut entries = vec![
+            entry("2024-01-15 10:23:45.100", "a"),
+            entry("2024-01-15 10:23:45.200", "b"),
+        ];
+        PageCursor::parse("2024-01-15 10:23:45.200").unwrap().apply(&mut entries);
+        assert_eq!(messages(&entries), ["a"]);
+        assert!(PageCursor::parse("2024-01-15 10:23:45.200#x").is_none());
+    }
+
+    #[test]
+    fn lag_is_measured_against_offsetless_timestamps() {
+        let now = chrono::NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()
+            .and_hms_milli_opt(10, 23, 47, 623).unwrap()
+            .and_local_timezone(chrono::Local).earliest().unwrap()
+            .fixed_offset();
+        assert_eq!(seconds_behind("2024-01-15 10:23:45.123", now), Some(2.5));
+        // Entries stamped after `now` don't produce a negative lag
+        assert_eq!(seconds_behind("2024-01-15 10:23:50.000", now), Some(0.0));
+        assert_eq!(seconds_behind("not a timestamp", now), None);
+    }
+}
@@ -1,14 +1,23 @@
 // Prevents additional console window on Windows in release
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod config;
+mod diagnostics;
+mod error;
 mod system_info;
 mod services;
 mod processes;
+mod security;
 mod logs;
+mod network;
+mod power;
+mod timings;
 mod tray;
 
 use serde::Serialize;
 
+use error::AppError;
+
 #[derive(Serialize)]
 struct ApiResult<T: Serialize> {
     ok: bool,
@@ -16,14 +25,71 @@ struct ApiResult<T: Serialize> {
     data: Option<T>,
     #[serde(skip_serializing_if = "Option::is_none")]
     error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'static str>,
 }
 
 fn ok_result<T: Serialize>(data: T) -> ApiResult<T> {
-    ApiResult { ok: true, data: Some(data), error: None }
+    ApiResult { ok: true, data: Some(data), error: None, code: None }
 }
 
-fn err_result<T: Serialize>(msg: String) -> ApiResult<T> {
-    ApiResult { ok: false, data: None, error: Some(msg) }
+fn err_result<T: Serialize>(err: AppError) -> ApiResult<T> {
+    ApiResult { ok: false, data: None, error: Some(err.message().to_string()), code: Some(err.code()) }
+}
+
+// ── Rate Limiting ────────────────────────────────────────────────────
+//
+// get_processes, get_services and query_logs each shell out or walk every
+// process. A repeat call with the same arguments inside the minimum interval
+// gets the previous result, so a runaway frontend timer can't pin the CPU.
+
+static RATE_LIMIT_CACHE: std::sync::LazyLock<
+    std::sync::Mutex<std::collections::HashMap<String, (std::time::Instant, std::sync::Arc<dyn std::any::Any + Send + Sync>)>>,
+> = std::sync::LazyLock::new(|| std::sync::Mutex::new(std::collections::HashMap::new()));
+
+const MAX_COMMAND_INTERVAL_MS: u64 = 10_000;
+
+static MIN_COMMAND_INTERVAL_MS: std::sync::LazyLock<std::sync::atomic::AtomicU64> =
+    std::sync::LazyLock::new(|| {
+        let ms = config::get().min_command_interval_ms.min(MAX_COMMAND_INTERVAL_MS);
+        std::sync::atomic::AtomicU64::new(ms)
+    });
+
+fn rate_limited_interval() -> std::time::Duration {
+    std::time::Duration::from_millis(MIN_COMMAND_INTERVAL_MS.load(std::sync::atomic::Ordering::Relaxed))
+}
+
+fn rate_limited_cached<T: Send + Sync + 'static>(key: &str) -> Option<std::sync::Arc<T>> {
+    let cache = RATE_LIMIT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let (at, value) = cache.get(key)?;
+    if at.elapsed() >= rate_limited_interval() {
+        return None;
+    }
+    value.clone().downcast::<T>().ok()
+}
+
+// Entries past the interval can never be served again, so each store drops
+// them; otherwise every distinct query_logs key would stay around forever
+fn rate_limited_store<T: Send + Sync + 'static>(key: String, value: T) -> std::sync::Arc<T> {
+    let min = rate_limited_interval();
+    let value = std::sync::Arc::new(value);
+    let mut cache = RATE_LIMIT_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    cache.retain(|_, (at, _)| at.elapsed() < min);
+    cache.insert(key, (std::time::Instant::now(), value.clone()));
+    value
+}
+
+#[tauri::command]
+fn set_min_command_interval(ms: u64) -> ApiResult<u64> {
+    let ms = ms.min(MAX_COMMAND_INTERVAL_MS);
+    if let Err(e) = config::update(|c| {
+        c.min_command_interval_ms = ms;
+        Ok(())
+    }) {
+        return err_result(e);
+    }
+    MIN_COMMAND_INTERVAL_MS.store(ms, std::sync::atomic::Ordering::Relaxed);
+    ok_result(ms)
 }
 
 // ── System Info Commands ─────────────────────────────────────────────
@@ -38,11 +104,80 @@ fn get_hardware_info() -> ApiResult<system_info::HardwareInfo> {
     ok_result(system_info::get_hardware_info())
 }
 
+#[tauri::command]
+fn set_stats_refresh_interval(ms: u64) -> ApiResult<u64> {
+    match system_info::set_refresh_interval(ms) {
+        Ok(applied) => ok_result(applied),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_core_details() -> ApiResult<Vec<system_info::CoreDetail>> {
+    ok_result(system_info::get_core_details())
+}
+
+#[tauri::command]
+fn get_per_core_history(seconds: Option<u64>) -> ApiResult<Vec<Vec<f32>>> {
+    ok_result(system_info::get_per_core_history(seconds.unwrap_or(60)))
+}
+
+#[tauri::command]
+fn get_system_trends() -> ApiResult<system_info::SystemTrends> {
+    ok_result(system_info::get_system_trends())
+}
+
+#[tauri::command]
+fn get_wired_breakdown() -> ApiResult<system_info::WiredBreakdown> {
+    ok_result(system_info::get_wired_breakdown())
+}
+
+#[tauri::command]
+fn get_health_score() -> ApiResult<system_info::HealthScore> {
+    ok_result(system_info::get_health_score())
+}
+
+#[tauri::command]
+fn get_display_state() -> ApiResult<system_info::DisplayState> {
+    ok_result(system_info::get_display_state())
+}
+
+#[tauri::command]
+async fn get_security_status() -> ApiResult<security::SecurityStatus> {
+    ok_result(security::get_security_status().await)
+}
+
+#[tauri::command]
+async fn get_privacy_indicators() -> ApiResult<security::PrivacyIndicators> {
+    ok_result(security::get_privacy_indicators().await)
+}
+
 // ── Services Commands ────────────────────────────────────────────────
 
 #[tauri::command]
-async fn get_services() -> ApiResult<Vec<services::ServiceInfo>> {
-    ok_result(services::list_services().await)
+async fn get_services() -> ApiResult<std::sync::Arc<Vec<services::ServiceInfo>>> {
+    if let Some(cached) = rate_limited_cached("get_services") {
+        return ok_result(cached);
+    }
+    let services = services::list_services().await;
+    ok_result(rate_limited_store("get_services".into(), services))
+}
+
+#[tauri::command]
+async fn rescan_services(app: tauri::AppHandle) -> ApiResult<Vec<services::ServiceInfo>> {
+    use tauri::Emitter;
+
+    let services = services::list_services().await;
+    let _ = app.emit("services-changed", &services);
+    ok_result(services)
+}
+
+#[tauri::command]
+async fn get_service(label: String) -> ApiResult<services::ServiceInfo> {
+    match services::get_service(&label).await {
+        Ok(service) => ok_result(service),
+        Err(e) => err_result(e),
+    }
 }
 
 #[tauri::command]
@@ -51,19 +186,67 @@ async fn get_service_detail(label: String) -> ApiResult<Option<services::Service
 }
 
 #[tauri::command]
-async fn manage_service(label: String, action: String, plist_path: Option<String>) -> ApiResult<()> {
+async fn manage_service(
+    label: String,
+    action: String,
+    plist_path: Option<String>,
+    force: Option<bool>,
+) -> ApiResult<Option<services::StopOutcome>> {
     let result = match action.as_str() {
-        "start" => services::start_service(&label).await,
-        "stop" => services::stop_service(&label).await,
-        "enable" => match plist_path {
-            Some(path) => services::enable_service(&path).await,
-            None => Err("plistPath required for enable".into()),
-        },
-        "disable" => services::disable_service(&label, plist_path.as_deref()).await,
-        _ => Err(format!("Unknown action: {}", action)),
+        "start" => services::start_service(&label).await.map(|_| None),
+        "stop" => services::stop_service(&label, force.unwrap_or(false)).await.map(Some),
+        "enable" => {
+            let path = match plist_path {
+                Some(path) => path,
+                None => match services::find_plist(&label).await {
+                    Ok(path) => path,
+                    Err(e) => return err_result(e),
+                },
+            };
+            services::enable_service(&path).await.map(|_| None)
+        }
+        "disable" => services::disable_service(&label, plist_path.as_deref()).await.map(|_| None),
+        _ => Err(AppError::InvalidArgument(format!("Unknown action: {}", action))),
     };
     match result {
-        Ok(()) => ok_result(()),
+        Ok(outcome) => ok_result(outcome),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn stop_service_and_wait(label: String, timeout_secs: Option<u64>) -> ApiResult<services::VerifiedStop> {
+    match services::stop_service_and_wait(&label, timeout_secs.unwrap_or(5)).await {
+        Ok(outcome) => ok_result(outcome),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn follow_service_output(app: tauri::AppHandle, label: String) -> ApiResult<Vec<String>> {
+    match services::follow_service_output(app, &label).await {
+        Ok(paths) => ok_result(paths),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn unfollow_service_output(label: String) -> ApiResult<bool> {
+    ok_result(services::unfollow_service_output(&label))
+}
+
+#[tauri::command]
+async fn edit_service_plist(label: String) -> ApiResult<services::PlistEdit> {
+    match services::edit_service_plist(&label).await {
+        Ok(edit) => ok_result(edit),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn set_flapping_threshold(restarts_per_minute: u32) -> ApiResult<u32> {
+    match services::set_flapping_threshold(restarts_per_minute) {
+        Ok(applied) => ok_result(applied),
         Err(e) => err_result(e),
     }
 }
@@ -71,8 +254,82 @@ async fn manage_service(label: String, action: String, plist_path: Option<String
 // ── Process Commands ─────────────────────────────────────────────────
 
 #[tauri::command]
-fn get_processes() -> ApiResult<Vec<processes::ProcessInfo>> {
-    ok_result(processes::list_processes())
+fn get_processes(detail: Option<bool>) -> ApiResult<std::sync::Arc<processes::ProcessList>> {
+    let detail = detail.unwrap_or(true);
+    let key = format!("get_processes:{}", detail);
+    if let Some(cached) = rate_limited_cached(&key) {
+        return ok_result(cached);
+    }
+    let list = processes::get_processes(detail);
+    ok_result(rate_limited_store(key, list))
+}
+
+#[tauri::command]
+fn capture_process_snapshot() -> ApiResult<u64> {
+    ok_result(processes::capture_process_snapshot())
+}
+
+#[tauri::command]
+fn compare_process_snapshots(id_a: u64, id_b: u64) -> ApiResult<processes::SnapshotComparison> {
+    match processes::compare_process_snapshots(id_a, id_b) {
+        Ok(comparison) => ok_result(comparison),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_process(pid: u32) -> ApiResult<Option<processes::ProcessInfo>> {
+    ok_result(processes::get_process(pid))
+}
+
+#[tauri::command]
+fn export_processes(path: String, overwrite: Option<bool>) -> ApiResult<usize> {
+    match processes::export_processes(&path, overwrite.unwrap_or(false)) {
+        Ok(rows) => ok_result(rows),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_process_detail(pid: u32) -> ApiResult<processes::ProcessDetail> {
+    match processes::get_process_detail(pid) {
+        Ok(detail) => ok_result(detail),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_top_processes(by: Option<String>, count: Option<usize>) -> ApiResult<Vec<processes::ProcessInfo>> {
+    match processes::top_processes(by.as_deref().unwrap_or("cpu"), count.unwrap_or(5)) {
+        Ok(procs) => ok_result(procs),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_usage_by_user() -> ApiResult<Vec<processes::UserUsage>> {
+    ok_result(processes::usage_by_user())
+}
+
+#[tauri::command]
+fn diff_processes(since_token: Option<u64>) -> ApiResult<processes::ProcessDiff> {
+    ok_result(processes::diff_processes(since_token))
+}
+
+#[tauri::command]
+fn set_process_refresh_mode(mode: String) -> ApiResult<()> {
+    match processes::set_refresh_mode(&mode) {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn set_collected_process_fields(fields: Vec<String>) -> ApiResult<()> {
+    match processes::set_collected_fields(&fields) {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
 }
 
 #[tauri::command]
@@ -83,14 +340,77 @@ fn kill_process(pid: u32, force: bool) -> ApiResult<()> {
     }
 }
 
+#[tauri::command]
+fn kill_process_tree(pid: u32, force: bool) -> ApiResult<Vec<processes::SignalResult>> {
+    match processes::kill_process_tree(pid, force) {
+        Ok(results) => ok_result(results),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn signal_matching(filter: processes::ProcessFilter, signal: String, confirm: Option<bool>) -> ApiResult<Vec<processes::SignalResult>> {
+    match processes::signal_matching(&filter, &signal, confirm.unwrap_or(false)) {
+        Ok(results) => ok_result(results),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn quit_app(bundle_id: String, force: Option<bool>) -> ApiResult<Vec<processes::QuitResult>> {
+    match processes::quit_app(&bundle_id, force.unwrap_or(false)).await {
+        Ok(results) => ok_result(results),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn reveal_process_in_finder(pid: u32) -> ApiResult<()> {
+    match processes::reveal_in_finder(pid) {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn reload_process(pid: u32) -> ApiResult<()> {
+    match processes::send_signal(pid, libc::SIGHUP) {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn dump_process_info(pid: u32) -> ApiResult<()> {
+    match processes::send_signal(pid, libc::SIGINFO) {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
 // ── Log Commands ─────────────────────────────────────────────────────
 
 #[tauri::command]
-fn start_log_stream() -> ApiResult<()> {
-    logs::start_log_stream();
+fn start_log_stream(app: tauri::AppHandle, predicate: Option<String>) -> ApiResult<()> {
+    logs::start_log_stream(app, predicate);
     ok_result(())
 }
 
+#[tauri::command]
+fn get_log_stream_stats() -> ApiResult<logs::LogStreamStats> {
+    ok_result(logs::get_log_stream_stats())
+}
+
+#[tauri::command]
+fn set_stream_predicate(predicate: Option<String>) -> ApiResult<bool> {
+    ok_result(logs::set_stream_predicate(predicate))
+}
+
+#[tauri::command]
+fn set_errors_only(enabled: bool) -> ApiResult<bool> {
+    ok_result(logs::set_errors_only(enabled))
+}
+
 #[tauri::command]
 fn stop_log_stream() -> ApiResult<()> {
     logs::stop_log_stream();
@@ -98,13 +418,77 @@ fn stop_log_stream() -> ApiResult<()> {
 }
 
 #[tauri::command]
-fn get_recent_logs(count: Option<usize>) -> ApiResult<Vec<logs::LogEntry>> {
-    ok_result(logs::get_recent_logs(count.unwrap_or(100)))
+async fn follow_process_logs(app: tauri::AppHandle, process_name: String) -> ApiResult<()> {
+    match logs::follow_process_logs(app, process_name).await {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn unfollow_process_logs(process_name: String) -> ApiResult<bool> {
+    ok_result(logs::unfollow_process_logs(&process_name))
 }
 
 #[tauri::command]
-async fn query_logs(minutes: Option<u32>, predicate: Option<String>) -> ApiResult<Vec<logs::LogEntry>> {
-    ok_result(logs::query_logs(minutes.unwrap_or(5), predicate.as_deref()).await)
+fn get_recent_logs(count: Option<usize>, level: Option<String>) -> ApiResult<Vec<logs::LogEntry>> {
+    match logs::get_recent_logs(count.unwrap_or(100), level.as_deref()) {
+        Ok(entries) => ok_result(entries),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn get_logs_since(cursor: Option<u64>) -> ApiResult<logs::LogsSince> {
+    ok_result(logs::get_logs_since(cursor.unwrap_or(0)))
+}
+
+#[tauri::command]
+fn get_recent_logs_for_processes(names: Vec<String>, count: Option<usize>) -> ApiResult<Vec<logs::LogEntry>> {
+    ok_result(logs::get_recent_logs_for_processes(&names, count.unwrap_or(100)))
+}
+
+#[tauri::command]
+async fn query_logs(
+    minutes: Option<u32>,
+    predicate: Option<String>,
+    filter_name: Option<String>,
+    limit: Option<usize>,
+    offset: Option<usize>,
+) -> ApiResult<std::sync::Arc<Vec<logs::LogEntry>>> {
+    // A saved filter stands in for an explicit predicate
+    let predicate = match filter_name {
+        Some(name) => match config::log_filter(&name) {
+            Ok(saved) => Some(saved),
+            Err(e) => return err_result(e),
+        },
+        None => predicate,
+    };
+    let (minutes, limit, offset) = (minutes.unwrap_or(5), limit.unwrap_or(logs::DEFAULT_QUERY_LIMIT), offset.unwrap_or(0));
+    let key = format!("query_logs:{}:{:?}:{}:{}", minutes, predicate, limit, offset);
+    if let Some(cached) = rate_limited_cached(&key) {
+        return ok_result(cached);
+    }
+    let entries = logs::query_logs(minutes, predicate.as_deref(), limit, offset).await;
+    ok_result(rate_limited_store(key, entries))
+}
+
+#[tauri::command]
+async fn query_logs_paged(
+    before_timestamp: Option<String>,
+    limit: Option<usize>,
+    predicate: Option<String>,
+) -> ApiResult<logs::LogPage> {
+    let limit = limit.unwrap_or(logs::DEFAULT_QUERY_LIMIT);
+    match logs::query_logs_paged(before_timestamp.as_deref(), limit, predicate.as_deref()).await {
+        Ok(page) => ok_result(page),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn query_logs_for_pid(pid: u32, minutes: Option<u32>) -> ApiResult<Vec<logs::LogEntry>> {
+    ok_result(logs::query_logs_for_pid(pid, minutes.unwrap_or(5)).await)
 }
 
 #[tauri::command]
@@ -116,6 +500,152 @@ fn get_active_log_processes() -> ApiResult<Vec<serde_json::Value>> {
     ok_result(result)
 }
 
+#[tauri::command]
+fn save_log_filter(name: String, predicate: String) -> ApiResult<std::collections::BTreeMap<String, String>> {
+    match config::save_log_filter(&name, &predicate) {
+        Ok(filters) => ok_result(filters),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn list_log_filters() -> ApiResult<std::collections::BTreeMap<String, String>> {
+    ok_result(config::get().log_filters)
+}
+
+#[tauri::command]
+fn delete_log_filter(name: String) -> ApiResult<std::collections::BTreeMap<String, String>> {
+    match config::delete_log_filter(&name) {
+        Ok(filters) => ok_result(filters),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn set_subsystem_log_level(subsystem: String, level: String) -> ApiResult<()> {
+    match logs::set_subsystem_log_level(&subsystem, &level).await {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+async fn reset_subsystem_log_level(subsystem: String) -> ApiResult<()> {
+    match logs::reset_subsystem_log_level(&subsystem).await {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+// ── Network Commands ─────────────────────────────────────────────────
+
+#[tauri::command]
+async fn get_default_route() -> ApiResult<network::DefaultRoute> {
+    ok_result(network::get_default_route().await)
+}
+
+#[tauri::command]
+async fn get_network_interfaces() -> ApiResult<Vec<network::NetworkInterface>> {
+    ok_result(network::get_network_interfaces().await)
+}
+
+#[tauri::command]
+async fn get_listening_ports() -> ApiResult<Vec<network::ListeningPort>> {
+    ok_result(network::get_listening_ports().await)
+}
+
+#[tauri::command]
+async fn get_network_summary() -> ApiResult<network::NetworkSummary> {
+    ok_result(network::get_network_summary().await)
+}
+
+#[tauri::command]
+async fn get_vpn_status() -> ApiResult<Vec<network::VpnConnection>> {
+    ok_result(network::get_vpn_status().await)
+}
+
+// ── Power Commands ───────────────────────────────────────────────────
+
+#[tauri::command]
+fn get_battery_info() -> ApiResult<Option<power::BatteryInfo>> {
+    ok_result(power::get_battery_info())
+}
+
+// ── Diagnostics Commands ─────────────────────────────────────────────
+
+#[tauri::command]
+fn get_gather_timings() -> ApiResult<timings::GatherTimings> {
+    ok_result(timings::get_gather_timings())
+}
+
+#[tauri::command]
+fn self_check() -> ApiResult<Vec<diagnostics::CheckResult>> {
+    ok_result(diagnostics::self_check())
+}
+
+#[tauri::command]
+async fn get_time_sync_status() -> ApiResult<diagnostics::TimeSyncStatus> {
+    ok_result(diagnostics::get_time_sync_status().await)
+}
+
+#[tauri::command]
+fn get_self_usage() -> ApiResult<diagnostics::SelfUsage> {
+    ok_result(diagnostics::get_self_usage())
+}
+
+#[cfg(debug_assertions)]
+#[tauri::command]
+fn run_cpu_stress(seconds: u64, threads: Option<usize>) -> ApiResult<usize> {
+    let threads = threads.unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1));
+    match diagnostics::run_cpu_stress(seconds, threads) {
+        Ok(started) => ok_result(started),
+        Err(e) => err_result(e),
+    }
+}
+
+#[cfg(debug_assertions)]
+#[tauri::command]
+fn stop_cpu_stress() -> ApiResult<()> {
+    diagnostics::stop_cpu_stress();
+    ok_result(())
+}
+
+#[tauri::command]
+fn check_full_disk_access() -> ApiResult<bool> {
+    ok_result(diagnostics::has_full_disk_access())
+}
+
+#[tauri::command]
+fn open_fda_settings() -> ApiResult<()> {
+    match diagnostics::open_fda_settings() {
+        Ok(()) => ok_result(()),
+        Err(e) => err_result(e),
+    }
+}
+
+// ── Favorites Commands ───────────────────────────────────────────────
+
+#[tauri::command]
+fn get_favorites() -> ApiResult<config::Favorites> {
+    ok_result(config::get().favorites)
+}
+
+#[tauri::command]
+fn add_favorite(kind: String, name: String) -> ApiResult<config::Favorites> {
+    match config::add_favorite(&kind, &name) {
+        Ok(favorites) => ok_result(favorites),
+        Err(e) => err_result(e),
+    }
+}
+
+#[tauri::command]
+fn remove_favorite(kind: String, name: String) -> ApiResult<config::Favorites> {
+    match config::remove_favorite(&kind, &name) {
+        Ok(favorites) => ok_result(favorites),
+        Err(e) => err_result(e),
+    }
+}
+
 // ── Window Commands ──────────────────────────────────────────────────
 
 #[tauri::command]
@@ -152,7 +682,7 @@ fn show_about_window(app: tauri::AppHandle) {
 }
 
 fn setup_menu(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
-    use tauri::{menu::*, Manager};
+    use tauri::menu::*;
     
     let app_menu = SubmenuBuilder::new(app, "Mac Dash")
         .about(Some(AboutMetadata::default()))
@@ -189,6 +719,35 @@ fn setup_menu(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+// ── Autostart ────────────────────────────────────────────────────────
+
+#[tauri::command]
+fn get_autostart_enabled(app: tauri::AppHandle) -> ApiResult<bool> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    // Reads the LaunchAgent plist from disk, so an externally removed agent reports false
+    match app.autolaunch().is_enabled() {
+        Ok(enabled) => ok_result(enabled),
+        Err(e) => err_result(AppError::CommandFailed(format!("Failed to read autostart state: {}", e))),
+    }
+}
+
+#[tauri::command]
+fn set_autostart(app: tauri::AppHandle, enabled: bool) -> ApiResult<bool> {
+    use tauri_plugin_autostart::ManagerExt;
+
+    let autolaunch = app.autolaunch();
+    let result = if enabled { autolaunch.enable() } else { autolaunch.disable() };
+    if let Err(e) = result {
+        return err_result(AppError::CommandFailed(format!("Failed to update autostart: {}", e)));
+    }
+    // Report what's actually on disk rather than echoing the request
+    match autolaunch.is_enabled() {
+        Ok(enabled) => ok_result(enabled),
+        Err(e) => err_result(AppError::CommandFailed(format!("Failed to read autostart state: {}", e))),
+    }
+}
+
 // ── Updater ──────────────────────────────────────────────────────────
 
 #[tauri::command]
@@ -253,14 +812,17 @@ fn main() {
             setup_menu(app)?;
             tray::setup_tray(app)?;
             // Start log stream automatically
-            logs::start_log_stream();
+            logs::start_log_stream(app.handle().clone(), None);
+            services::start_flap_detector(app.handle().clone());
             
             // Check for updates on startup (async, non-blocking)
             let app_handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 use tauri_plugin_updater::UpdaterExt;
                 if let Ok(updater) = app_handle.updater() {
-                    let _ = updater.check().await;
+                    if let Ok(Some(update)) = updater.check().await {
+                        let _ = tray::show_update_available(&app_handle, &update.version);
+                    }
                 }
             });
             
@@ -269,18 +831,86 @@ fn main() {
         .invoke_handler(tauri::generate_handler![
             get_system_info,
             get_hardware_info,
+            get_system_trends,
+            get_per_core_history,
+            get_display_state,
+            get_wired_breakdown,
+            get_health_score,
+            get_security_status,
+            get_privacy_indicators,
+            get_core_details,
+            set_stats_refresh_interval,
+            set_min_command_interval,
             get_services,
+            rescan_services,
+            get_service,
             get_service_detail,
             manage_service,
+            stop_service_and_wait,
+            follow_service_output,
+            unfollow_service_output,
+            edit_service_plist,
+            set_flapping_threshold,
             get_processes,
+            get_process,
+            get_process_detail,
+            export_processes,
+            get_top_processes,
+            set_process_refresh_mode,
+            set_collected_process_fields,
+            diff_processes,
+            capture_process_snapshot,
+            compare_process_snapshots,
+            get_usage_by_user,
             kill_process,
+            kill_process_tree,
+            signal_matching,
+            quit_app,
+            reveal_process_in_finder,
+            reload_process,
+            dump_process_info,
             start_log_stream,
+            get_log_stream_stats,
+            set_stream_predicate,
+            set_errors_only,
             stop_log_stream,
+            follow_process_logs,
+            unfollow_process_logs,
             get_recent_logs,
+            get_recent_logs_for_processes,
+            get_logs_since,
+            set_subsystem_log_level,
+            reset_subsystem_log_level,
             query_logs,
+            query_logs_paged,
+            query_logs_for_pid,
             get_active_log_processes,
+            save_log_filter,
+            list_log_filters,
+            delete_log_filter,
+            get_default_route,
+            get_vpn_status,
+            get_network_interfaces,
+            get_listening_ports,
+            get_network_summary,
+            get_battery_info,
+            get_gather_timings,
+            self_check,
+            get_self_usage,
+            get_time_sync_status,
+            #[cfg(debug_assertions)]
+            run_cpu_stress,
+            #[cfg(debug_assertions)]
+            stop_cpu_stress,
+            check_full_disk_access,
+            open_fda_settings,
+            get_favorites,
+            add_favorite,
+            remove_favorite,
             begin_window_drag,
             show_about_window,
+            get_autostart_enabled,
+            set_autostart,
             check_for_updates,
             install_update,
         ])
@@ -0,0 +1,342 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use tokio::process::Command;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultRoute {
+    pub interface: Option<String>,
+    pub gateway_v4: Option<String>,
+    pub gateway_v6: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VpnConnection {
+    pub name: String,
+    pub kind: String,
+    pub connected: bool,
+    pub interface: Option<String>,
+}
+
+#[derive(Serialize, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub struct ListeningPort {
+    pub proto: String, // "tcp" | "udp"
+    pub address: String,
+    pub port: u16,
+    pub pid: i32,
+    pub process: String,
+}
+
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkSummary {
+    pub established: usize,
+    pub listening: usize,
+    pub time_wait: usize,
+    pub other: usize, // SYN_SENT, CLOSE_WAIT, FIN_WAIT_*, ...
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkInterface {
+    pub name: String,
+    pub active: bool,
+    pub media_type: Option<String>, // hardware port, e.g. "Wi-Fi", "Thunderbolt Bridge"; None for virtual interfaces
+    pub link_speed_mbps: Option<u32>, // negotiated wired speed; None for Wi-Fi and virtual interfaces
+}
+
+async fn exec_cmd(args: &[&str]) -> String {
+    let output = Command::new(args[0])
+        .args(&args[1..])
+        .output()
+        .await;
+    match output {
+        Ok(o) => String::from_utf8_lossy(&o.stdout).trim().to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+// Parses `route -n get` output into (gateway, interface)
+fn parse_route_get(output: &str) -> (Option<String>, Option<String>) {
+    let mut gateway = None;
+    let mut interface = None;
+    for line in output.lines() {
+        let t = line.trim();
+        if let Some(v) = t.strip_prefix("gateway:") { gateway = Some(v.trim().to_string()); }
+        else if let Some(v) = t.strip_prefix("interface:") { interface = Some(v.trim().to_string()); }
+    }
+    (gateway, interface)
+}
+
+pub async fn get_default_route() -> DefaultRoute {
+    let (v4, v6) = tokio::join!(
+        exec_cmd(&["route", "-n", "get", "default"]),
+        exec_cmd(&["route", "-n", "get", "-inet6", "default"]),
+    );
+    let (gateway_v4, iface_v4) = parse_route_get(&v4);
+    let (gateway_v6, iface_v6) = parse_route_get(&v6);
+
+    DefaultRoute {
+        interface: iface_v4.or(iface_v6),
+        gateway_v4,
+        gateway_v6,
+    }
+}
+
+// Parses a `scutil --nc list` row, e.g.
+// `* (Connected)      3F2A... VPN (com.wireguard.macos) "Office"   [VPN/WireGuard]`
+// into (uuid, name, kind, connected)
+fn parse_nc_line(line: &str) -> Option<(String, String, String, bool)> {
+    let open = line.find('(')?;
+    let close = open + line[open..].find(')')?;
+    let status = &line[open + 1..close];
+    let rest = line[close + 1..].trim_start();
+    let uuid = rest.split_whitespace().next()?.to_string();
+
+    let name_start = rest.find('"')? + 1;
+    let name_end = name_start + rest[name_start..].find('"')?;
+    let name = rest[name_start..name_end].to_string();
+
+    let kind = rest[name_end..]
+        .rfind('[')
+        .and_then(|i| {
+            let tail = &rest[name_end + i + 1..];
+            tail.find(']').map(|j| tail[..j].to_string())
+        })
+        .unwrap_or_default();
+
+    Some((uuid, name, kind, status == "Connected"))
+}
+
+pub async fn get_vpn_status() -> Vec<VpnConnection> {
+    let output = exec_cmd(&["scutil", "--nc", "list"]).await;
+    let mut connections = Vec::new();
+
+    // First line is the "Available network connection services..." header
+    for line in output.lines().skip(1) {
+        let Some((uuid, name, kind, connected)) = parse_nc_line(line) else { continue };
+        let interface = if connected {
+            let status = exec_cmd(&["scutil", "--nc", "status", &uuid]).await;
+            status.lines()
+                .find_map(|l| l.trim().strip_prefix("InterfaceName : "))
+                .map(|v| v.trim().to_string())
+        } else {
+            None
+        };
+        connections.push(VpnConnection { name, kind, connected, interface });
+    }
+    connections
+}
+
+// Parses `lsof -F pcPn` field output: a `p`/`c` pair per process followed by
+// `P`/`n` pairs per socket
+fn parse_lsof_sockets(output: &str) -> Vec<ListeningPort> {
+    let mut ports = Vec::new();
+    let mut pid = 0;
+    let mut process = String::new();
+    let mut proto = String::new();
+
+    for line in output.lines() {
+        let (tag, value) = line.split_at(line.len().min(1));
+        match tag {
+            "p" => pid = value.parse().unwrap_or(0),
+            "c" => process = value.to_string(),
+            "P" => proto = value.to_lowercase(),
+            "n" => {
+                // Connected UDP sockets ("a:1->b:2") aren't listening
+                if value.contains("->") { continue; }
+                let Some((addr, port)) = value.rsplit_once(':') else { continue };
+                let Ok(port) = port.parse::<u16>() else { continue };
+                ports.push(ListeningPort {
+                    proto: proto.clone(),
+                    address: addr.trim_start_matches('[').trim_end_matches(']').to_string(),
+                    port,
+                    pid,
+                    process: process.clone(),
+                });
+            }
+            _ => {}
+        }
+    }
+    ports
+}
+
+pub async fn get_listening_ports() -> Vec<ListeningPort> {
+    let (tcp, udp) = tokio::join!(
+        exec_cmd(&["lsof", "-iTCP", "-sTCP:LISTEN", "-nP", "-F", "pcPn"]),
+        exec_cmd(&["lsof", "-iUDP", "-nP", "-F", "pcPn"]),
+    );
+
+    let mut ports = parse_lsof_sockets(&tcp);
+    ports.extend(parse_lsof_sockets(&udp));
+
+    // Dual-stack listeners show up once per address family as "*"
+    let mut seen = std::collections::HashSet::new();
+    ports.retain(|p| seen.insert(p.clone()));
+    ports.sort_by(|a, b| a.port.cmp(&b.port).then_with(|| a.proto.cmp(&b.proto)));
+    ports
+}
+
+// Ports each of `pids` listens on, TCP and UDP together, for annotating
+// services. Only those pids are scanned, which is far cheaper than the
+// system-wide listing get_listening_ports does.
+pub async fn listening_ports_by_pid(pids: &[i32]) -> HashMap<i32, Vec<u16>> {
+    let mut by_pid: HashMap<i32, Vec<u16>> = HashMap::new();
+    if pids.is_empty() {
+        return by_pid;
+    }
+    let pids = pids.iter().map(|p| p.to_string()).collect::<Vec<_>>().join(",");
+    // -a ANDs the pid and socket selections; lsof ORs them by default
+    let (tcp, udp) = tokio::join!(
+        exec_cmd(&["lsof", "-a", "-iTCP", "-sTCP:LISTEN", "-p", &pids, "-nP", "-F", "pcPn"]),
+        exec_cmd(&["lsof", "-a", "-iUDP", "-p", &pids, "-nP", "-F", "pcPn"]),
+    );
+    for p in parse_lsof_sockets(&tcp).into_iter().chain(parse_lsof_sockets(&udp)) {
+        let ports = by_pid.entry(p.pid).or_default();
+        if !ports.contains(&p.port) {
+            ports.push(p.port);
+        }
+    }
+    by_pid
+}
+
+// Parses `networksetup -listallhardwareports` blocks, e.g.
+// `Hardware Port: Wi-Fi` / `Device: en0`, into device -> port name
+fn parse_hardware_ports(output: &str) -> HashMap<String, String> {
+    let mut ports = HashMap::new();
+    let mut port = None;
+    for line in output.lines() {
+        if let Some(v) = line.strip_prefix("Hardware Port: ") {
+            port = Some(v.trim().to_string());
+        } else if let Some(device) = line.strip_prefix("Device: ") {
+            if let Some(port) = port.take() {
+                ports.insert(device.trim().to_string(), port);
+            }
+        }
+    }
+    ports
+}
+
+// The subtype in an ifconfig media line: "autoselect (1000baseT <full-duplex>)"
+// -> 1000, "10Gbase-T <full-duplex>" -> 10000. Wi-Fi reports just "autoselect".
+fn parse_media_speed(media: &str) -> Option<u32> {
+    let subtype = media.split(['(', ' ']).find(|w| w.to_lowercase().contains("base"))?;
+    let digits: String = subtype.chars().take_while(|c| c.is_ascii_digit()).collect();
+    let n: u32 = digits.parse().ok()?;
+    match subtype[digits.len()..].chars().next() {
+        Some('G') | Some('g') => Some(n * 1000),
+        _ => Some(n),
+    }
+}
+
+// Splits `ifconfig` output into (name, active, media line) per interface;
+// interface headers are the unindented lines
+fn parse_ifconfig(output: &str) -> Vec<(String, bool, Option<String>)> {
+    let mut interfaces: Vec<(String, bool, Option<String>)> = Vec::new();
+    for line in output.lines() {
+        if !line.starts_with(['\t', ' ']) {
+            if let Some((name, _)) = line.split_once(':') {
+                interfaces.push((name.to_string(), false, None));
+            }
+            continue;
+        }
+        let Some(current) = interfaces.last_mut() else { continue };
+        let t = line.trim();
+        if let Some(v) = t.strip_prefix("status: ") { current.1 = v == "active"; }
+        else if let Some(v) = t.strip_prefix("media: ") { current.2 = Some(v.to_string()); }
+    }
+    interfaces
+}
+
+pub async fn get_network_interfaces() -> Vec<NetworkInterface> {
+    let (ifconfig, hardware) = tokio::join!(
+        exec_cmd(&["ifconfig"]),
+        exec_cmd(&["networksetup", "-listallhardwareports"]),
+    );
+    let ports = parse_hardware_ports(&hardware);
+
+    parse_ifconfig(&ifconfig).into_iter().map(|(name, active, media)| {
+        let media_type = ports.get(&name).cloned();
+        // Bridges and tunnels carry a media line too, but it describes nothing physical
+        let link_speed_mbps = media.as_deref()
+            .filter(|_| media_type.is_some() && active)
+            .and_then(parse_media_speed);
+        NetworkInterface { name, active, media_type, link_speed_mbps }
+    }).collect()
+}
+
+// Tallies the state column of `netstat -an -p tcp`, e.g.
+// `tcp4  0  0  192.168.1.5.51234  17.57.144.10.443  ESTABLISHED`
+fn parse_netstat_states(output: &str) -> NetworkSummary {
+    let mut summary = NetworkSummary::default();
+    for line in output.lines().filter(|l| l.starts_with("tcp")) {
+        match line.split_whitespace().last() {
+            Some("ESTABLISHED") => summary.established += 1,
+            Some("LISTEN") => summary.listening += 1,
+            Some("TIME_WAIT") => summary.time_wait += 1,
+            Some(_) => summary.other += 1,
+            None => {}
+        }
+    }
+    summary
+}
+
+pub async fn get_network_summary() -> NetworkSummary {
+    parse_netstat_states(&exec_cmd(&["netstat", "-an", "-p", "tcp"]).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_lsof_field_output() {
+        // `lsof -i -nP -F pcPn` output; lsof always adds the `f` (fd) lines
+        let output = "p1234\ncControlCenter\nf10\nPTCP\nn*:7000\nf11\nPTCP\nn*:5000\n\
+                      p567\ncmDNSResponder\nf7\nPUDP\nn*:5353\nf8\nPUDP\nn10.0.0.5:55000->17.253.4.125:123\n\
+                      p890\nccupsd\nf5\nPTCP\nn[::1]:631\nf6\nPTCP\nn127.0.0.1:631\n";
+        let ports = parse_lsof_sockets(output);
+        let summary: Vec<(&str, &str, u16, i32, &str)> = ports.iter()
+            .map(|p| (p.proto.as_str(), p.address.as_str(), p.port, p.pid, p.process.as_str()))
+            .collect();
+        assert_eq!(summary, vec![
+            ("tcp", "*", 7000, 1234, "ControlCenter"),
+            ("tcp", "*", 5000, 1234, "ControlCenter"),
+            ("udp", "*", 5353, 567, "mDNSResponder"),
+            ("tcp", "::1", 631, 890, "cupsd"),
+            ("tcp", "127.0.0.1", 631, 890, "cupsd"),
+        ]);
+    }
+
+    #[test]
+    fn empty_lsof_output_has_no_ports() {
+        assert!(parse_lsof_sockets("").is_empty());
+    }
+
+    #[test]
+    fn parses_scutil_nc_rows() {
+        let connected = r#"* (Connected)      3F2A6C1E-9B4D-4E7A-8C21-5D0F6B7A9E12 VPN (com.wireguard.macos) "Office"                 [VPN/WireGuard]"#;
+        assert_eq!(parse_nc_line(connected), Some((
+            "3F2A6C1E-9B4D-4E7A-8C21-5D0F6B7A9E12".into(), "Office".into(), "VPN/WireGuard".into(), true,
+        )));
+
+        let idle = r#"* (Disconnected)   8B1D2F40-1C3E-4A5B-9D6F-7E8A9B0C1D2E IPSec              "Home (IKEv2)"           [IPSec]"#;
+        assert_eq!(parse_nc_line(idle), Some((
+            "8B1D2F40-1C3E-4A5B-9D6F-7E8A9B0C1D2E".into(), "Home (IKEv2)".into(), "IPSec".into(), false,
+        )));
+
+        assert_eq!(parse_nc_line("Available network connection services in the current set (*=enabled):"), None);
+    }
+
+    #[test]
+    fn parses_ifconfig_media_speed() {
+        assert_eq!(parse_media_speed("autoselect (1000baseT <full-duplex>)"), Some(1000));
+        assert_eq!(parse_media_speed("autoselect (2500Base-T <full-duplex,flow-control>)"), Some(2500));
+        assert_eq!(parse_media_speed("10Gbase-T <full-duplex>"), Some(10000));
+        // Wi-Fi and unplugged ports carry no speed
+        assert_eq!(parse_media_speed("autoselect"), None);
+        assert_eq!(parse_media_speed("autoselect (none)"), None);
+    }
+}
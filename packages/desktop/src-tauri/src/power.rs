@@ -0,0 +1,98 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct BatteryInfo {
+    pub percent: u8,
+    pub charging: bool,
+    pub external_power: bool,
+    pub fully_charged: bool,
+    pub time_remaining_minutes: Option<u32>, // None on AC or while macOS is still estimating
+    pub time_to_full_minutes: Option<u32>, // None unless charging with an estimate
+    pub power_watts: Option<f64>, // positive when charging, negative when discharging
+    pub power_source: String, // "AC Adapter" | "Battery"
+    pub adapter_watts: Option<u32>, // the charger's rating; None on battery
+}
+
+// AppleSmartBattery reports this while it's still estimating
+const TIME_UNKNOWN: u64 = 65535;
+
+// The battery's registry entry, as a plist dictionary; None on desktop Macs
+fn smart_battery() -> Option<plist::Dictionary> {
+    let output = std::process::Command::new("ioreg")
+        .args(["-a", "-r", "-c", "AppleSmartBattery"])
+        .output()
+        .ok()?;
+    let val = plist::Value::from_reader(std::io::Cursor::new(output.stdout)).ok()?;
+    val.into_array()?.into_iter().next()?.into_dictionary()
+}
+
+// `pmset -g adapter` prints e.g. ` Wattage = 96W`
+fn pmset_adapter_watts() -> Option<u32> {
+    let output = std::process::Command::new("pmset").args(["-g", "adapter"]).output().ok()?;
+    String::from_utf8_lossy(&output.stdout).lines()
+        .find_map(|l| l.trim().strip_prefix("Wattage = "))
+        .and_then(|w| w.trim_end_matches('W').parse().ok())
+}
+
+fn adapter_watts(dict: &plist::Dictionary) -> Option<u32> {
+    dict.get("AdapterDetails")
+        .and_then(|v| v.as_dictionary())
+        .and_then(|d| d.get("Watts"))
+        .and_then(|v| v.as_unsigned_integer())
+        .map(|w| w as u32)
+        .filter(|w| *w > 0)
+        .or_else(pmset_adapter_watts)
+}
+
+pub fn get_battery_info() -> Option<BatteryInfo> {
+    let dict = smart_battery()?;
+    let uint = |key: &str| dict.get(key).and_then(|v| v.as_unsigned_integer());
+    // Negative currents come back as 64-bit two's complement in an unsigned integer
+    let int = |key: &str| dict.get(key).and_then(|v| {
+        v.as_signed_integer().or_else(|| v.as_unsigned_integer().map(|u| u as i64))
+    });
+    let flag = |key: &str| dict.get(key).and_then(|v| v.as_boolean()).unwrap_or(false);
+
+    // Apple Silicon reports CurrentCapacity as a percentage with MaxCapacity
+    // 100; Intel reports both in mAh. The ratio works for either.
+    let current = uint("CurrentCapacity")?;
+    let max = uint("MaxCapacity").filter(|m| *m > 0)?;
+    let external_power = flag("ExternalConnected");
+
+    Some(BatteryInfo {
+        percent: (current * 100 / max).min(100) as u8,
+        charging: flag("IsCharging"),
+        external_power,
+        fully_charged: flag("FullyCharged"),
+        time_remaining_minutes: uint("TimeRemaining")
+            .filter(|t| !external_power && *t != TIME_UNKNOWN)
+            .map(|t| t as u32),
+        time_to_full_minutes: uint("AvgTimeToFull")
+            .filter(|t| flag("IsCharging") && !flag("FullyCharged") && *t != TIME_UNKNOWN)
+            .map(|t| t as u32),
+        // mA × mV; InstantAmperage reacts to load right away, Amperage is averaged
+        power_watts: int("InstantAmperage").or_else(|| int("Amperage"))
+            .zip(int("Voltage"))
+            .map(|(ma, mv)| (ma as f64 * mv as f64 / 1_000_000.0 * 100.0).round() / 100.0),
+        power_source: if external_power { "AC Adapter" } else { "Battery" }.into(),
+        adapter_watts: if external_power { adapter_watts(&dict) } else { None },
+    })
+}
+
+// One-line summary for the tray: "Charging 87%", "64% – 3:12 remaining"
+pub fn battery_summary(info: &BatteryInfo) -> String {
+    if info.charging {
+        match info.time_to_full_minutes {
+            Some(mins) => format!("Charging {}% – full in {}:{:02}", info.percent, mins / 60, mins % 60),
+            None => format!("Charging {}%", info.percent),
+        }
+    } else if info.external_power {
+        format!("On power adapter {}%", info.percent)
+    } else {
+        match info.time_remaining_minutes {
+            Some(mins) => format!("{}% – {}:{:02} remaining", info.percent, mins / 60, mins % 60),
+            None => format!("{}% – estimating time remaining", info.percent),
+        }
+    }
+}
@@ -1,6 +1,12 @@
-use serde::Serialize;
-use sysinfo::{ProcessesToUpdate, ProcessRefreshKind, System};
-use std::sync::Mutex;
+use serde::{Deserialize, Serialize};
+use sysinfo::{Pid, ProcessRefreshKind, ProcessStatus, ProcessesToUpdate, System, UpdateKind};
+use std::collections::HashMap;
+use std::io::Write;
+use std::sync::{Mutex, TryLockError};
+use std::time::Instant;
+
+use crate::error::{AppError, AppResult};
+use crate::timings::{self, Stage};
 
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -8,7 +14,9 @@ pub struct ProcessInfo {
     pub pid: u32,
     pub ppid: u32,
     pub uid: u32,
-    pub cpu: f32,
+    pub cpu: f32,            // summed across cores, like `top`; 400 = four cores busy
+    pub cpu_normalized: f32, // share of the whole machine, 0-100
+    pub cpu_time_secs: f64, // user + system over the process's lifetime
     pub mem: f64,
     pub rss: u64, // bytes
     pub elapsed: String,
@@ -16,6 +24,76 @@ pub struct ProcessInfo {
     pub path: String,
     pub args: String,
     pub user: String,
+    pub state: String, // "running" | "sleeping" | "idle" | "stopped" | "zombie" | ...
+    pub being_traced: bool, // attached to a debugger or ptrace'd
+    pub pgid: Option<u32>,
+    pub sid: Option<u32>,
+    pub nice: i32,
+    pub scheduling_policy: String, // "timeshare" | "round-robin" | "fifo" | "unknown"
+    pub fd_count: Option<u32>,     // None when we can't inspect the process (not ours)
+    pub socket_count: Option<u32>,
+    pub gpu_percent: f32, // Apple Silicon only; 0 when the process has no GPU clients
+    pub wakeups_per_sec: Option<f64>, // interrupt + idle wakeups; None on the first sample or when not ours
+    pub cpu_user: Option<f32>,   // same scale as `cpu`, split by where the time went; None like wakeups
+    pub cpu_system: Option<f32>,
+    pub is_favorite: bool,
+    pub bundle_id: Option<String>,
+    pub app_name: Option<String>, // outermost .app, so helpers group under their app
+    pub activation_policy: String, // "regular" | "accessory" (LSUIElement) | "prohibited"
+}
+
+// The columns the overview table needs; the rest of ProcessInfo comes from
+// get_process_detail when a row is opened
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessSummary {
+    pub pid: u32,
+    pub ppid: u32,
+    pub cpu: f32,
+    pub cpu_normalized: f32,
+    pub mem: f64,
+    pub rss: u64,
+    pub command: String,
+    pub state: String,
+    pub is_favorite: bool,
+}
+
+impl From<&ProcessInfo> for ProcessSummary {
+    fn from(p: &ProcessInfo) -> Self {
+        ProcessSummary {
+            pid: p.pid,
+            ppid: p.ppid,
+            cpu: p.cpu,
+            cpu_normalized: p.cpu_normalized,
+            mem: p.mem,
+            rss: p.rss,
+            command: p.command.clone(),
+            state: p.state.clone(),
+            is_favorite: p.is_favorite,
+        }
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(untagged)]
+pub enum ProcessRows {
+    Detailed(Vec<ProcessInfo>),
+    Summary(Vec<ProcessSummary>),
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessList {
+    pub refreshed_at: i64, // unix millis of the gather; older than the call when a cached list was served
+    pub processes: ProcessRows,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessDetail {
+    #[serde(flatten)]
+    pub info: ProcessInfo,
+    pub cwd: Option<String>, // None for other users' processes
 }
 
 static PROC_SYS: std::sync::LazyLock<Mutex<System>> = std::sync::LazyLock::new(|| {
@@ -24,53 +102,1317 @@ static PROC_SYS: std::sync::LazyLock<Mutex<System>> = std::sync::LazyLock::new(|
     Mutex::new(sys)
 });
 
+// Single-pid lookups refresh here rather than in PROC_SYS; refreshing a pid
+// resets its CPU baseline, which would skew that pid in the next full gather
+static LOOKUP_SYS: std::sync::LazyLock<Mutex<System>> = std::sync::LazyLock::new(|| {
+    let mut sys = System::new();
+    sys.refresh_memory();
+    Mutex::new(sys)
+});
+
+// Result of the most recent gather, handed to callers that arrive while
+// another gather still holds PROC_SYS
+static LAST_PROCESSES: std::sync::LazyLock<Mutex<Vec<ProcessInfo>>> =
+    std::sync::LazyLock::new(|| Mutex::new(Vec::new()));
+
+// Zombies seen in the most recent process refresh, for the SystemStats headline
+static ZOMBIES: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+// When LAST_PROCESSES was gathered, unix millis
+static REFRESHED_AT: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(0);
+
+// Length of the most recent process list; 0 until the first gather finishes
+static PROCESS_COUNT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ProcessDiff {
+    pub token: u64,
+    pub full: bool, // since_token was unknown/expired, so everything is in `appeared`
+    pub appeared: Vec<ProcessInfo>,
+    pub disappeared: Vec<u32>,
+    pub changed: Vec<ProcessInfo>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct UserUsage {
+    pub user: String,
+    pub uid: u32,
+    pub cpu: f32,
+    pub rss: u64, // bytes
+    pub process_count: usize,
+}
+
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum QuitOutcome {
+    Quit,         // exited after the app was asked to quit
+    Terminated,   // exited after SIGTERM
+    Killed,       // exited after SIGKILL
+    StillRunning,
+    Failed,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct QuitResult {
+    pub pid: u32,
+    pub command: String,
+    pub outcome: QuitOutcome,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotComparison {
+    pub added: Vec<ProcessInfo>,   // as in the second capture
+    pub removed: Vec<ProcessInfo>, // as in the first capture
+    pub changed: Vec<ProcessInfo>, // as in the second capture
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SignalResult {
+    pub pid: u32,
+    pub command: String,
+    pub error: Option<String>, // None when the signal was delivered
+}
+
+// Every given field has to match; an empty filter is rejected rather than
+// treated as "everything"
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProcessFilter {
+    pub name: Option<String>, // command name, case-insensitive
+    pub user: Option<String>,
+    pub min_cpu: Option<f32>, // same scale as ProcessInfo::cpu
+}
+
+// More matches than this need an explicit confirm
+const SIGNAL_CONFIRM_THRESHOLD: usize = 5;
+
+// Never signalled in bulk; taking any of these down logs the user out or panics the machine
+const CRITICAL_PROCESSES: [&str; 6] = ["kernel_task", "launchd", "WindowServer", "loginwindow", "opendirectoryd", "mds"];
+
+// Left alone by bulk signals and tree kills: the critical processes and mac-dash itself
+fn is_protected(p: &ProcessInfo) -> bool {
+    p.pid <= 1 || p.pid == std::process::id() || CRITICAL_PROCESSES.contains(&p.command.as_str())
+}
+
+// How long an app gets to quit (and save state) before we fall back to signals
+const QUIT_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+const SIGNAL_GRACE: std::time::Duration = std::time::Duration::from_secs(2);
+
+// What we remember per pid for diffing; the command name catches pid reuse
+struct SnapshotEntry {
+    command: String,
+    cpu: f32,
+    mem: f64,
+}
+
+// Changes smaller than these (percentage points) don't count as "changed"
+const DIFF_CPU_THRESHOLD: f32 = 1.0;
+const DIFF_MEM_THRESHOLD: f64 = 0.1;
+const SNAPSHOT_CAPACITY: usize = 8;
+
+type Snapshot = HashMap<u32, SnapshotEntry>;
+
+#[derive(Default)]
+struct SnapshotStore {
+    last_token: u64,
+    recent: std::collections::VecDeque<(u64, Snapshot)>, // oldest first
+}
+
+static SNAPSHOTS: std::sync::LazyLock<Mutex<SnapshotStore>> =
+    std::sync::LazyLock::new(|| Mutex::new(SnapshotStore::default()));
+
+// User-taken captures for before/after comparisons, by id; the oldest is
+// dropped past CAPTURE_CAPACITY
+static CAPTURES: std::sync::LazyLock<Mutex<(u64, std::collections::BTreeMap<u64, Vec<ProcessInfo>>)>> =
+    std::sync::LazyLock::new(|| Mutex::new((0, std::collections::BTreeMap::new())));
+
+const CAPTURE_CAPACITY: usize = 16;
+
+// uid -> login name; accounts don't get renamed under a running session
+static USERNAMES: std::sync::LazyLock<Mutex<HashMap<u32, String>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Default)]
+struct BundleInfo {
+    bundle_id: Option<String>,
+    app_name: Option<String>,
+    activation_policy: Option<&'static str>, // None without a bundle
+}
+
+// Keyed by executable path; bundles don't change under a running process
+static BUNDLES: std::sync::LazyLock<Mutex<HashMap<String, BundleInfo>>> =
+    std::sync::LazyLock::new(|| Mutex::new(HashMap::new()));
+
+// Accumulated GPU time (ns) per pid from the previous sample, for diffing
+static LAST_GPU: std::sync::LazyLock<Mutex<(HashMap<u32, u64>, Instant)>> =
+    std::sync::LazyLock::new(|| Mutex::new((HashMap::new(), Instant::now())));
+
+// Accumulated rusage counters per pid from the previous sample, for diffing
+static LAST_RUSAGE: std::sync::LazyLock<Mutex<(HashMap<u32, RusageCounters>, Instant)>> =
+    std::sync::LazyLock::new(|| Mutex::new((HashMap::new(), Instant::now())));
+
+// In light mode only CPU and memory are sampled each refresh; exe, args and
+// user are read once per process and reused, since they don't change
+static LIGHT_REFRESH: std::sync::atomic::AtomicBool =
+    std::sync::atomic::AtomicBool::new(false);
+
+pub fn set_refresh_mode(mode: &str) -> AppResult<()> {
+    let light = match mode {
+        "full" => false,
+        "light" => true,
+        _ => return Err(AppError::InvalidArgument(format!("Unknown refresh mode: {}", mode))),
+    };
+    LIGHT_REFRESH.store(light, std::sync::atomic::Ordering::Relaxed);
+    Ok(())
+}
+
+// Optional per-process work, switched off for columns the UI isn't showing.
+// Skipped fields are left empty (or None/0). Only the UI's own list honors
+// the selection; internal callers always gather everything.
+#[derive(Clone, Copy)]
+struct CollectedFields {
+    path: bool,
+    args: bool,
+    user: bool,
+    fds: bool,    // fdCount, socketCount
+    bundle: bool, // bundleId, appName, activationPolicy; needs the exe path
+    gpu: bool,
+    rusage: bool, // wakeupsPerSec, cpuUser, cpuSystem
+}
+
+impl CollectedFields {
+    const ALL: CollectedFields = CollectedFields {
+        path: true, args: true, user: true, fds: true, bundle: true, gpu: true, rusage: true,
+    };
+}
+
+const COLLECTABLE_FIELDS: [&str; 7] = ["path", "args", "user", "fds", "bundle", "gpu", "rusage"];
+
+static COLLECTED: std::sync::LazyLock<Mutex<CollectedFields>> =
+    std::sync::LazyLock::new(|| Mutex::new(CollectedFields::ALL));
+
+fn collected_fields() -> CollectedFields {
+    *COLLECTED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Takes the full set of optional fields to gather; anything not named is skipped
+pub fn set_collected_fields(fields: &[String]) -> AppResult<()> {
+    if let Some(unknown) = fields.iter().find(|f| !COLLECTABLE_FIELDS.contains(&f.as_str())) {
+        return Err(AppError::InvalidArgument(format!(
+            "Unknown process field: {} (expected one of {})", unknown, COLLECTABLE_FIELDS.join(", ")
+        )));
+    }
+    let has = |name: &str| fields.iter().any(|f| f == name);
+    *COLLECTED.lock().unwrap_or_else(|e| e.into_inner()) = CollectedFields {
+        path: has("path"),
+        args: has("args"),
+        user: has("user"),
+        fds: has("fds"),
+        bundle: has("bundle"),
+        gpu: has("gpu"),
+        rusage: has("rusage"),
+    };
+    Ok(())
+}
+
+fn refresh_kind(fields: CollectedFields) -> ProcessRefreshKind {
+    let light = LIGHT_REFRESH.load(std::sync::atomic::Ordering::Relaxed);
+    let update = if light { UpdateKind::OnlyIfNotSet } else { UpdateKind::Always };
+    let mut kind = ProcessRefreshKind::nothing().with_cpu().with_memory();
+    if !light {
+        kind = kind.with_disk_usage();
+    }
+    if fields.path || fields.bundle {
+        kind = kind.with_exe(update);
+    }
+    if fields.args {
+        kind = kind.with_cmd(update);
+    }
+    if fields.user {
+        kind = kind.with_user(update);
+    }
+    kind
+}
+
+// Per-pid values that come from diffing against the previous full gather
+#[derive(Default)]
+struct Sampled {
+    gpu_percent: f32,
+    wakeups_per_sec: Option<f64>,
+    cpu_user: Option<f32>,
+    cpu_system: Option<f32>,
+}
+
+fn build_info(
+    p: &sysinfo::Process,
+    total_mem: u64,
+    cores: f32,
+    favorites: &[String],
+    sampled: Sampled,
+    fields: CollectedFields,
+) -> ProcessInfo {
+    let pid = p.pid().as_u32();
+    let ppid = p.parent().map(|pp| pp.as_u32()).unwrap_or(0);
+    let uid = p.user_id().map(|u| **u).unwrap_or(0);
+    let rss = p.memory();
+    let mem_pct = if total_mem > 0 { (rss as f64 / total_mem as f64) * 100.0 } else { 0.0 };
+    let exe = p.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
+    let name = p.name().to_string_lossy().to_string();
+    let args = if fields.args {
+        p.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect::<Vec<_>>().join(" ")
+    } else {
+        String::new()
+    };
+    let fds = if fields.fds { list_fds(pid) } else { None };
+    let is_favorite = favorites.contains(&name);
+    let bundle = if fields.bundle { bundle_info(&exe) } else { BundleInfo::default() };
+    let cmd_path = if fields.path { exe } else { String::new() };
+    let kinfo = kinfo(pid);
+
+    ProcessInfo {
+        pid, ppid, uid,
+        cpu: p.cpu_usage(),
+        cpu_normalized: p.cpu_usage() / cores,
+        cpu_time_secs: p.accumulated_cpu_time() as f64 / 1000.0,
+        mem: (mem_pct * 10.0).round() / 10.0,
+        rss,
+        elapsed: format_elapsed(p.run_time()),
+        command: name,
+        path: cmd_path,
+        args,
+        user: if fields.user { username(uid) } else { String::new() },
+        state: status_name(p.status()).into(),
+        being_traced: kinfo.as_ref().map(|k| k.kp_proc.p_flag & P_TRACED != 0).unwrap_or(false),
+        pgid: kinfo.as_ref().map(|k| k.kp_eproc.e_pgid as u32),
+        sid: read_sid(pid),
+        nice: read_nice(pid),
+        scheduling_policy: read_scheduling_policy(pid).into(),
+        fd_count: fds.as_ref().map(|f| f.len() as u32),
+        socket_count: fds.as_ref().map(|f| {
+            f.iter().filter(|fd| fd.proc_fdtype == libc::PROX_FDTYPE_SOCKET as u32).count() as u32
+        }),
+        gpu_percent: sampled.gpu_percent,
+        wakeups_per_sec: sampled.wakeups_per_sec,
+        cpu_user: sampled.cpu_user,
+        cpu_system: sampled.cpu_system,
+        is_favorite,
+        bundle_id: bundle.bundle_id,
+        app_name: bundle.app_name,
+        activation_policy: bundle.activation_policy.unwrap_or("prohibited").into(),
+    }
+}
+
+impl From<&ProcessInfo> for Sampled {
+    fn from(p: &ProcessInfo) -> Self {
+        Sampled {
+            gpu_percent: p.gpu_percent,
+            wakeups_per_sec: p.wakeups_per_sec,
+            cpu_user: p.cpu_user,
+            cpu_system: p.cpu_system,
+        }
+    }
+}
+
+// The sampled rates of the last gather's rows, by pid
+fn last_sampled() -> HashMap<u32, Sampled> {
+    LAST_PROCESSES.lock().unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .map(|p| (p.pid, Sampled::from(p)))
+        .collect()
+}
+
+// For internal callers (export, diffs, signals), with every field filled in
+// whatever the UI has switched off. The sampled rates are carried over from
+// the last UI poll rather than re-sampled, so those calls don't shrink the
+// window the UI's rates are measured over.
 pub fn list_processes() -> Vec<ProcessInfo> {
-    let mut sys = PROC_SYS.lock().unwrap();
-    sys.refresh_processes(ProcessesToUpdate::All, true);
+    gather(CollectedFields::ALL, false)
+}
+
+fn gather(fields: CollectedFields, resample: bool) -> Vec<ProcessInfo> {
+    let started = Instant::now();
+    let mut sys = match PROC_SYS.try_lock() {
+        Ok(guard) => guard,
+        Err(TryLockError::Poisoned(e)) => e.into_inner(),
+        // The cached rows may lack fields internal callers rely on, so only
+        // the UI's list takes them
+        Err(TryLockError::WouldBlock) if !resample => PROC_SYS.lock().unwrap_or_else(|e| e.into_inner()),
+        Err(TryLockError::WouldBlock) => {
+            let cached = LAST_PROCESSES.lock().unwrap_or_else(|e| e.into_inner());
+            if !cached.is_empty() {
+                return cached.clone();
+            }
+            drop(cached);
+            // Nothing cached yet (first gather still running), so wait for it
+            PROC_SYS.lock().unwrap_or_else(|e| e.into_inner())
+        }
+    };
+    sys.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh_kind(fields));
     let total_mem = sys.total_memory();
+    let mut sampled: HashMap<u32, Sampled> = if resample {
+        let gpu = if fields.gpu { sample_gpu_percent() } else { HashMap::new() };
+        let rates = if fields.rusage {
+            sample_rusage(sys.processes().keys().map(|p| p.as_u32()))
+        } else {
+            HashMap::new()
+        };
+        sys.processes().keys().map(|p| {
+            let pid = p.as_u32();
+            let rate = rates.get(&pid);
+            (pid, Sampled {
+                gpu_percent: gpu.get(&pid).copied().unwrap_or(0.0),
+                wakeups_per_sec: rate.map(|r| r.wakeups_per_sec),
+                cpu_user: rate.map(|r| r.cpu_user),
+                cpu_system: rate.map(|r| r.cpu_system),
+            })
+        }).collect()
+    } else {
+        last_sampled()
+    };
+    let favorites = crate::config::get().favorites.processes;
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
 
     let mut procs: Vec<ProcessInfo> = sys.processes().values().map(|p| {
-        let pid = p.pid().as_u32();
-        let ppid = p.parent().map(|pp| pp.as_u32()).unwrap_or(0);
-        let uid = p.user_id().map(|u| **u).unwrap_or(0);
-        let rss = p.memory();
-        let mem_pct = if total_mem > 0 { (rss as f64 / total_mem as f64) * 100.0 } else { 0.0 };
-        let cmd_path = p.exe().map(|e| e.to_string_lossy().to_string()).unwrap_or_default();
-        let name = p.name().to_string_lossy().to_string();
-        let args = p.cmd().iter().map(|s| s.to_string_lossy().to_string()).collect::<Vec<_>>().join(" ");
-
-        ProcessInfo {
-            pid, ppid, uid,
-            cpu: p.cpu_usage(),
-            mem: (mem_pct * 10.0).round() / 10.0,
-            rss,
-            elapsed: format_elapsed(p.run_time()),
-            command: name,
-            path: cmd_path,
-            args,
-            user: format!("{}", uid),
-        }
+        let sampled = sampled.remove(&p.pid().as_u32()).unwrap_or_default();
+        build_info(p, total_mem, cores, &favorites, sampled, fields)
     }).collect();
 
     procs.sort_by(|a, b| b.cpu.partial_cmp(&a.cpu).unwrap_or(std::cmp::Ordering::Equal));
+    let zombies = procs.iter().filter(|p| p.state == "zombie").count();
+    ZOMBIES.store(zombies, std::sync::atomic::Ordering::Relaxed);
+    PROCESS_COUNT.store(procs.len(), std::sync::atomic::Ordering::Relaxed);
+    REFRESHED_AT.store(chrono::Utc::now().timestamp_millis(), std::sync::atomic::Ordering::Relaxed);
+    timings::record(Stage::ProcessEnumeration, started);
+    *LAST_PROCESSES.lock().unwrap_or_else(|e| e.into_inner()) = procs.clone();
     procs
 }
 
-pub fn kill_process(pid: u32, force: bool) -> Result<(), String> {
+// Refreshes just this pid. The sampled rates (GPU, wakeups, user/system
+// split) are diffs between full gathers, so they come from the last list row,
+// and so does CPU the first time a pid is looked up, before there's a baseline.
+pub fn get_process(pid: u32) -> Option<ProcessInfo> {
+    let last = LAST_PROCESSES.lock().unwrap_or_else(|e| e.into_inner())
+        .iter()
+        .find(|p| p.pid == pid)
+        .cloned();
+    let sampled = last.as_ref().map(Sampled::from).unwrap_or_default();
+    let favorites = crate::config::get().favorites.processes;
+    let cores = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1) as f32;
+
+    let sys_pid = Pid::from_u32(pid);
+    let mut sys = LOOKUP_SYS.lock().unwrap_or_else(|e| e.into_inner());
+    let first_lookup = sys.process(sys_pid).is_none();
+    let fields = collected_fields();
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[sys_pid]), true, refresh_kind(fields));
+    let p = sys.process(sys_pid)?;
+    let mut info = build_info(p, sys.total_memory(), cores, &favorites, sampled, fields);
+    if let Some(last) = last.filter(|_| first_lookup) {
+        info.cpu = last.cpu;
+        info.cpu_normalized = last.cpu_normalized;
+    }
+    Some(info)
+}
+
+// The UI's periodic poll, and the only gather that advances the GPU and
+// rusage baselines
+pub fn get_processes(detail: bool) -> ProcessList {
+    let procs = gather(collected_fields(), true);
+    let processes = if detail {
+        ProcessRows::Detailed(procs)
+    } else {
+        ProcessRows::Summary(procs.iter().map(ProcessSummary::from).collect())
+    };
+    ProcessList {
+        refreshed_at: REFRESHED_AT.load(std::sync::atomic::Ordering::Relaxed),
+        processes,
+    }
+}
+
+fn process_cwd(pid: u32) -> Option<String> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = LOOKUP_SYS.lock().unwrap_or_else(|e| e.into_inner());
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_cwd(UpdateKind::Always),
+    );
+    sys.process(pid).and_then(|p| p.cwd()).map(|c| c.to_string_lossy().to_string())
+}
+
+// The row the summary list was built from, so the detail matches what was
+// clicked; a fresh gather only if the pid is newer than the last list
+pub fn get_process_detail(pid: u32) -> AppResult<ProcessDetail> {
+    let cached = LAST_PROCESSES.lock().unwrap_or_else(|e| e.into_inner())
+        .iter().find(|p| p.pid == pid).cloned();
+    let info = cached
+        .or_else(|| list_processes().into_iter().find(|p| p.pid == pid))
+        .ok_or_else(|| AppError::NotFound(format!("Process {} not found", pid)))?;
+    Ok(ProcessDetail { cwd: process_cwd(pid), info })
+}
+
+// RFC 4180: quote fields containing a separator, quote or line break, and
+// double any quotes inside
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+const CSV_HEADER: &str = "pid,ppid,user,command,cpu,mem,rss,state,elapsed,path,args";
+
+// Writes the current list to `path`, returning how many rows went in. The
+// path must be absolute, and an existing file is only replaced when
+// `overwrite` is set.
+pub fn export_processes(path: &str, overwrite: bool) -> AppResult<usize> {
+    let path = std::path::Path::new(path);
+    if !path.is_absolute() {
+        return Err(AppError::InvalidArgument(format!("Export path must be absolute: {}", path.display())));
+    }
+    let procs = list_processes();
+    let mut csv = String::from(CSV_HEADER);
+    csv.push('\n');
+    for p in &procs {
+        let row = [
+            p.pid.to_string(),
+            p.ppid.to_string(),
+            csv_field(&p.user),
+            csv_field(&p.command),
+            format!("{:.1}", p.cpu),
+            format!("{:.1}", p.mem),
+            p.rss.to_string(),
+            p.state.clone(),
+            csv_field(&p.elapsed),
+            csv_field(&p.path),
+            csv_field(&p.args),
+        ];
+        csv.push_str(&row.join(","));
+        csv.push('\n');
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .truncate(true)
+        .create(true)
+        .create_new(!overwrite)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::AlreadyExists => AppError::InvalidArgument(format!("{} already exists", path.display())),
+            _ => AppError::from(e),
+        })?;
+    file.write_all(csv.as_bytes())?;
+    Ok(procs.len())
+}
+
+fn snapshot_of(procs: &[ProcessInfo]) -> Snapshot {
+    procs.iter()
+        .map(|p| (p.pid, SnapshotEntry { command: p.command.clone(), cpu: p.cpu, mem: p.mem }))
+        .collect()
+}
+
+// Rows of `procs` that are new since `prev`, and those that moved past the thresholds
+fn appeared_and_changed(prev: &Snapshot, procs: &[ProcessInfo]) -> (Vec<ProcessInfo>, Vec<ProcessInfo>) {
+    let mut appeared = Vec::new();
+    let mut changed = Vec::new();
+    for p in procs {
+        match prev.get(&p.pid) {
+            Some(old) if old.command == p.command => {
+                if (p.cpu - old.cpu).abs() >= DIFF_CPU_THRESHOLD
+                    || (p.mem - old.mem).abs() >= DIFF_MEM_THRESHOLD
+                {
+                    changed.push(p.clone());
+                }
+            }
+            _ => appeared.push(p.clone()),
+        }
+    }
+    (appeared, changed)
+}
+
+pub fn diff_processes(since_token: Option<u64>) -> ProcessDiff {
+    let procs = list_processes();
+    let snapshot = snapshot_of(&procs);
+
+    let mut store = SNAPSHOTS.lock().unwrap_or_else(|e| e.into_inner());
+    let previous = since_token.and_then(|t| store.recent.iter().find(|(token, _)| *token == t));
+
+    let diff = match previous {
+        Some((_, prev)) => {
+            let (appeared, changed) = appeared_and_changed(prev, &procs);
+            let disappeared = prev.iter()
+                .filter(|(pid, old)| snapshot.get(pid).map(|s| s.command != old.command).unwrap_or(true))
+                .map(|(pid, _)| *pid)
+                .collect();
+            ProcessDiff { token: 0, full: false, appeared, disappeared, changed }
+        }
+        None => ProcessDiff { token: 0, full: true, appeared: procs, disappeared: Vec::new(), changed: Vec::new() },
+    };
+
+    store.last_token += 1;
+    let token = store.last_token;
+    store.recent.push_back((token, snapshot));
+    if store.recent.len() > SNAPSHOT_CAPACITY {
+        store.recent.pop_front();
+    }
+    ProcessDiff { token, ..diff }
+}
+
+pub fn capture_process_snapshot() -> u64 {
+    let procs = list_processes();
+    let mut captures = CAPTURES.lock().unwrap_or_else(|e| e.into_inner());
+    let (last_id, stored) = &mut *captures;
+    *last_id += 1;
+    stored.insert(*last_id, procs);
+    while stored.len() > CAPTURE_CAPACITY {
+        stored.pop_first();
+    }
+    *last_id
+}
+
+pub fn compare_process_snapshots(id_a: u64, id_b: u64) -> AppResult<SnapshotComparison> {
+    let captures = CAPTURES.lock().unwrap_or_else(|e| e.into_inner());
+    let get = |id: u64| captures.1.get(&id)
+        .ok_or_else(|| AppError::NotFound(format!("No process snapshot {} (expired or never taken)", id)));
+    let (a, b) = (get(id_a)?, get(id_b)?);
+
+    let (added, changed) = appeared_and_changed(&snapshot_of(a), b);
+    let after = snapshot_of(b);
+    let removed = a.iter()
+        .filter(|p| after.get(&p.pid).map(|s| s.command != p.command).unwrap_or(true))
+        .cloned()
+        .collect();
+    Ok(SnapshotComparison { added, removed, changed })
+}
+
+pub fn usage_by_user() -> Vec<UserUsage> {
+    let mut by_uid: HashMap<u32, UserUsage> = HashMap::new();
+    for p in list_processes() {
+        let usage = by_uid.entry(p.uid).or_insert_with(|| UserUsage {
+            user: p.user.clone(),
+            uid: p.uid,
+            cpu: 0.0,
+            rss: 0,
+            process_count: 0,
+        });
+        usage.cpu += p.cpu;
+        usage.rss += p.rss;
+        usage.process_count += 1;
+    }
+    let mut users: Vec<UserUsage> = by_uid.into_values().collect();
+    users.sort_by_key(|u| std::cmp::Reverse(u.rss));
+    users
+}
+
+pub fn zombie_count() -> usize {
+    ZOMBIES.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+// So the headline count matches the list the Processes panel is showing
+pub fn process_count() -> Option<usize> {
+    Some(PROCESS_COUNT.load(std::sync::atomic::Ordering::Relaxed)).filter(|n| *n > 0)
+}
+
+pub fn process_name(pid: u32) -> Option<String> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = LOOKUP_SYS.lock().unwrap_or_else(|e| e.into_inner());
+    sys.refresh_processes_specifics(ProcessesToUpdate::Some(&[pid]), true, ProcessRefreshKind::nothing());
+    sys.process(pid).map(|p| p.name().to_string_lossy().to_string())
+}
+
+fn process_path(pid: u32) -> Option<std::path::PathBuf> {
+    let pid = Pid::from_u32(pid);
+    let mut sys = LOOKUP_SYS.lock().unwrap_or_else(|e| e.into_inner());
+    sys.refresh_processes_specifics(
+        ProcessesToUpdate::Some(&[pid]),
+        true,
+        ProcessRefreshKind::nothing().with_exe(UpdateKind::Always),
+    );
+    sys.process(pid).and_then(|p| p.exe()).map(|e| e.to_path_buf())
+}
+
+pub fn reveal_in_finder(pid: u32) -> AppResult<()> {
+    // Kernel tasks and other users' processes often have no readable path,
+    // and `open -R ""` would just open an empty Finder window
+    let path = process_path(pid)
+        .filter(|p| !p.as_os_str().is_empty())
+        .ok_or_else(|| AppError::NotFound(format!("No executable path for process {}", pid)))?;
+    let output = std::process::Command::new("open").arg("-R").arg(&path).output()?;
+    if !output.status.success() {
+        return Err(AppError::from_stderr(&String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(())
+}
+
+pub fn top_processes(by: &str, count: usize) -> AppResult<Vec<ProcessInfo>> {
+    let mut procs = list_processes();
+    match by {
+        "cpu" => {} // already sorted by CPU
+        "mem" => procs.sort_by_key(|p| std::cmp::Reverse(p.rss)),
+        "cpu_time" => procs.sort_by(|a, b| b.cpu_time_secs.total_cmp(&a.cpu_time_secs)),
+        "wakeups" => procs.sort_by(|a, b| {
+            b.wakeups_per_sec.unwrap_or(0.0).total_cmp(&a.wakeups_per_sec.unwrap_or(0.0))
+        }),
+        _ => return Err(AppError::InvalidArgument(format!("Unknown sort key: {}", by))),
+    }
+    procs.truncate(count);
+    Ok(procs)
+}
+
+pub fn kill_process(pid: u32, force: bool) -> AppResult<()> {
     let output = std::process::Command::new("kill")
         .arg(if force { "-9" } else { "-15" })
         .arg(pid.to_string())
-        .output()
-        .map_err(|e| e.to_string())?;
+        .output()?;
 
     if !output.status.success() {
-        let err = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        Err(err)
+        Err(AppError::from_stderr(&String::from_utf8_lossy(&output.stderr)))
     } else {
         Ok(())
     }
 }
 
+// Parent pid -> child pids, from one process list snapshot
+fn children_by_parent(procs: &[ProcessInfo]) -> HashMap<u32, Vec<u32>> {
+    let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+    for p in procs.iter().filter(|p| p.pid != p.ppid) {
+        children.entry(p.ppid).or_default().push(p.pid);
+    }
+    children
+}
+
+// `pid` and everything below it, deepest first, so each child comes before its parent
+fn descendants_bottom_up(pid: u32, children: &HashMap<u32, Vec<u32>>) -> Vec<u32> {
+    let mut order = Vec::new();
+    let mut stack = vec![(pid, false)];
+    while let Some((current, expanded)) = stack.pop() {
+        if expanded {
+            order.push(current);
+            continue;
+        }
+        stack.push((current, true));
+        for child in children.get(&current).into_iter().flatten() {
+            // pid 0/1 parent everything; never walk back up into them
+            if *child > 1 && !order.contains(child) {
+                stack.push((*child, false));
+            }
+        }
+    }
+    order
+}
+
+// Signals the whole tree children-first, so the parent can't respawn workers
+// that were already killed
+pub fn kill_process_tree(pid: u32, force: bool) -> AppResult<Vec<SignalResult>> {
+    if pid <= 1 {
+        return Err(AppError::InvalidArgument(format!("Refusing to kill process tree of pid {}", pid)));
+    }
+    let procs = list_processes();
+    let root = procs.iter().find(|p| p.pid == pid)
+        .ok_or_else(|| AppError::NotFound(format!("No such process: {}", pid)))?;
+    if is_protected(root) {
+        return Err(AppError::InvalidArgument(format!("Refusing to kill process tree of {} ({})", root.command, pid)));
+    }
+    let commands: HashMap<u32, String> = procs.iter().map(|p| (p.pid, p.command.clone())).collect();
+    let children = children_by_parent(&procs);
+    let order = descendants_bottom_up(pid, &children);
+
+    // A protected descendant keeps its whole subtree; killing what's below
+    // WindowServer or loginwindow would take the session down piecemeal
+    let protected: Vec<u32> = procs.iter()
+        .filter(|p| is_protected(p) && order.contains(&p.pid))
+        .map(|p| p.pid)
+        .collect();
+    let spared: std::collections::HashSet<u32> = protected.iter()
+        .flat_map(|p| descendants_bottom_up(*p, &children))
+        .collect();
+    let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+
+    Ok(order.into_iter().map(|p| SignalResult {
+        pid: p,
+        command: commands.get(&p).cloned().unwrap_or_default(),
+        error: if protected.contains(&p) {
+            Some("Refusing to signal a critical process".into())
+        } else if spared.contains(&p) {
+            Some("Skipped: runs under a critical process".into())
+        } else {
+            send_signal(p, signal).err().map(|e| e.message().to_string())
+        },
+    }).collect())
+}
+
+fn signal_by_name(name: &str) -> Option<libc::c_int> {
+    let name = name.trim().to_ascii_uppercase();
+    Some(match name.strip_prefix("SIG").unwrap_or(&name) {
+        "HUP" => libc::SIGHUP,
+        "INT" => libc::SIGINT,
+        "QUIT" => libc::SIGQUIT,
+        "KILL" => libc::SIGKILL,
+        "USR1" => libc::SIGUSR1,
+        "USR2" => libc::SIGUSR2,
+        "TERM" => libc::SIGTERM,
+        "STOP" => libc::SIGSTOP,
+        "CONT" => libc::SIGCONT,
+        "INFO" => libc::SIGINFO,
+        _ => return None,
+    })
+}
+
+impl ProcessFilter {
+    fn is_empty(&self) -> bool {
+        self.name.is_none() && self.user.is_none() && self.min_cpu.is_none()
+    }
+
+    fn matches(&self, p: &ProcessInfo) -> bool {
+        self.name.as_ref().map(|n| p.command.eq_ignore_ascii_case(n)).unwrap_or(true)
+            && self.user.as_ref().map(|u| p.user == *u).unwrap_or(true)
+            && self.min_cpu.map(|c| p.cpu >= c).unwrap_or(true)
+    }
+}
+
+pub fn signal_matching(filter: &ProcessFilter, signal: &str, confirm: bool) -> AppResult<Vec<SignalResult>> {
+    if filter.is_empty() {
+        return Err(AppError::InvalidArgument("Filter needs at least one of name, user or minCpu".into()));
+    }
+    let sig = signal_by_name(signal)
+        .ok_or_else(|| AppError::InvalidArgument(format!("Unknown signal: {}", signal)))?;
+    let targets: Vec<ProcessInfo> = list_processes().into_iter()
+        .filter(|p| !is_protected(p))
+        .filter(|p| filter.matches(p))
+        .collect();
+    if targets.len() > SIGNAL_CONFIRM_THRESHOLD && !confirm {
+        return Err(AppError::InvalidArgument(format!(
+            "{} processes match; pass confirm to signal all of them", targets.len()
+        )));
+    }
+
+    Ok(targets.into_iter().map(|p| SignalResult {
+        error: send_signal(p.pid, sig).err().map(|e| e.message().to_string()),
+        pid: p.pid,
+        command: p.command,
+    }).collect())
+}
+
+pub fn send_signal(pid: u32, signal: libc::c_int) -> AppResult<()> {
+    if unsafe { libc::kill(pid as libc::pid_t, signal) } == 0 {
+        return Ok(());
+    }
+    let err = std::io::Error::last_os_error();
+    Err(match err.raw_os_error() {
+        Some(libc::ESRCH) => AppError::NotFound(format!("No such process: {}", pid)),
+        Some(libc::EPERM) => AppError::PermissionDenied(format!("Not permitted to signal process {}", pid)),
+        _ => AppError::from(err),
+    })
+}
+
+fn username(uid: u32) -> String {
+    if let Some(name) = USERNAMES.lock().unwrap_or_else(|e| e.into_inner()).get(&uid) {
+        return name.clone();
+    }
+    // Unknown uids (deleted accounts) show as the number, like `ps` does
+    let name = lookup_username(uid).unwrap_or_else(|| uid.to_string());
+    USERNAMES.lock().unwrap_or_else(|e| e.into_inner()).insert(uid, name.clone());
+    name
+}
+
+fn lookup_username(uid: u32) -> Option<String> {
+    let mut pwd: libc::passwd = unsafe { std::mem::zeroed() };
+    let mut buf = vec![0 as libc::c_char; 1024];
+    let mut result: *mut libc::passwd = std::ptr::null_mut();
+    let ret = unsafe {
+        libc::getpwuid_r(uid as libc::uid_t, &mut pwd, buf.as_mut_ptr(), buf.len(), &mut result)
+    };
+    if ret != 0 || result.is_null() { return None; }
+    let name = unsafe { std::ffi::CStr::from_ptr(pwd.pw_name) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+fn is_alive(pid: u32) -> bool {
+    // Signal 0 only checks; EPERM still means the process exists
+    !matches!(send_signal(pid, 0), Err(AppError::NotFound(_)))
+}
+
+async fn wait_for_exit(pids: &[u32], timeout: std::time::Duration) {
+    let deadline = Instant::now() + timeout;
+    while Instant::now() < deadline && pids.iter().any(|p| is_alive(*p)) {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+    }
+}
+
+// Cmd+Q semantics for a whole bundle: the app is asked to quit through Apple
+// Events first, so Electron/Chromium apps shut their helpers down cleanly and
+// don't report an unexpected quit on next launch. Whatever is left gets
+// SIGTERM, or SIGKILL when `force` is set.
+pub async fn quit_app(bundle_id: &str, force: bool) -> AppResult<Vec<QuitResult>> {
+    if bundle_id.is_empty() || !bundle_id.chars().all(|c| c.is_ascii_alphanumeric() || "._-".contains(c)) {
+        return Err(AppError::InvalidArgument(format!("Invalid bundle id: {}", bundle_id)));
+    }
+    let procs: Vec<(u32, String)> = list_processes().into_iter()
+        .filter(|p| p.bundle_id.as_deref() == Some(bundle_id))
+        .map(|p| (p.pid, p.command))
+        .collect();
+    if procs.is_empty() {
+        return Err(AppError::NotFound(format!("No running processes for {}", bundle_id)));
+    }
+    let pids: Vec<u32> = procs.iter().map(|(pid, _)| *pid).collect();
+
+    // Fails for background-only bundles with no scripting support; signals still follow
+    let script = format!("tell application id \"{}\" to quit", bundle_id);
+    let asked = tokio::process::Command::new("osascript")
+        .args(["-e", &script])
+        .output()
+        .await
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if asked {
+        wait_for_exit(&pids, QUIT_GRACE).await;
+    }
+
+    let mut results: HashMap<u32, QuitResult> = HashMap::new();
+    let mut signalled = Vec::new();
+    for (pid, command) in &procs {
+        let mut result = QuitResult { pid: *pid, command: command.clone(), outcome: QuitOutcome::Quit, error: None };
+        if is_alive(*pid) {
+            let signal = if force { libc::SIGKILL } else { libc::SIGTERM };
+            match send_signal(*pid, signal) {
+                Ok(()) => signalled.push(*pid),
+                Err(AppError::NotFound(_)) => {} // exited just now
+                Err(e) => {
+                    result.outcome = QuitOutcome::Failed;
+                    result.error = Some(e.message().to_string());
+                }
+            }
+        }
+        results.insert(*pid, result);
+    }
+
+    wait_for_exit(&signalled, SIGNAL_GRACE).await;
+    for pid in signalled {
+        if let Some(result) = results.get_mut(&pid) {
+            result.outcome = if is_alive(pid) {
+                QuitOutcome::StillRunning
+            } else if force {
+                QuitOutcome::Killed
+            } else {
+                QuitOutcome::Terminated
+            };
+        }
+    }
+
+    Ok(pids.iter().filter_map(|pid| results.remove(pid)).collect())
+}
+
+fn read_nice(pid: u32) -> i32 {
+    // getpriority can legitimately return -1, so errno has to be checked
+    unsafe {
+        *libc::__error() = 0;
+        let prio = libc::getpriority(libc::PRIO_PROCESS, pid as libc::id_t);
+        if prio == -1 && *libc::__error() != 0 { 0 } else { prio }
+    }
+}
+
+fn task_info(pid: u32) -> Option<libc::proc_taskinfo> {
+    let mut info: libc::proc_taskinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_taskinfo>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTASKINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    if ret == size { Some(info) } else { None }
+}
+
+fn bsd_info(pid: u32) -> Option<libc::proc_bsdinfo> {
+    let mut info: libc::proc_bsdinfo = unsafe { std::mem::zeroed() };
+    let size = std::mem::size_of::<libc::proc_bsdinfo>() as libc::c_int;
+    let ret = unsafe {
+        libc::proc_pidinfo(
+            pid as libc::c_int,
+            libc::PROC_PIDTBSDINFO,
+            0,
+            &mut info as *mut _ as *mut libc::c_void,
+            size,
+        )
+    };
+    if ret == size { Some(info) } else { None }
+}
+
+// Seconds since the process started, without a full process refresh
+pub fn uptime_secs(pid: u32) -> Option<u64> {
+    let started = bsd_info(pid)?.pbi_start_tvsec;
+    let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).ok()?.as_secs();
+    Some(now.saturating_sub(started))
+}
+
+// From <sys/proc.h>; libc doesn't export the p_flag bits
+const P_TRACED: libc::c_int = 0x0000_0800;
+
+fn kinfo(pid: u32) -> Option<libc::kinfo_proc> {
+    let mut mib = [libc::CTL_KERN, libc::KERN_PROC, libc::KERN_PROC_PID, pid as libc::c_int];
+    let mut info: libc::kinfo_proc = unsafe { std::mem::zeroed() };
+    let mut size = std::mem::size_of::<libc::kinfo_proc>();
+    let ret = unsafe {
+        libc::sysctl(
+            mib.as_mut_ptr(),
+            mib.len() as libc::c_uint,
+            &mut info as *mut _ as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    // A pid that's gone returns success with size 0
+    if ret == 0 && size > 0 { Some(info) } else { None }
+}
+
+fn read_sid(pid: u32) -> Option<u32> {
+    let sid = unsafe { libc::getsid(pid as libc::pid_t) };
+    if sid < 0 { None } else { Some(sid as u32) }
+}
+
+pub fn thread_count(pid: u32) -> Option<u32> {
+    task_info(pid).map(|t| t.pti_threadnum.max(0) as u32)
+}
+
+pub fn rusage(pid: u32) -> Option<libc::rusage_info_v2> {
+    let mut info: libc::rusage_info_v2 = unsafe { std::mem::zeroed() };
+    let ret = unsafe {
+        libc::proc_pid_rusage(
+            pid as libc::c_int,
+            libc::RUSAGE_INFO_V2,
+            &mut info as *mut _ as *mut libc::rusage_info_t,
+        )
+    };
+    if ret == 0 { Some(info) } else { None }
+}
+
+// Every pid on the system, straight from the kernel
+fn all_pids() -> Vec<libc::pid_t> {
+    let count = unsafe { libc::proc_listallpids(std::ptr::null_mut(), 0) };
+    if count <= 0 { return Vec::new(); }
+    // Leave headroom for processes started between the two calls
+    let mut pids: Vec<libc::pid_t> = vec![0; count as usize + 64];
+    let size = (pids.len() * std::mem::size_of::<libc::pid_t>()) as libc::c_int;
+    let n = unsafe { libc::proc_listallpids(pids.as_mut_ptr() as *mut libc::c_void, size) };
+    pids.truncate(n.max(0) as usize);
+    pids
+}
+
+// Wired memory attributable to processes; the rest of the system's wired
+// total belongs to the kernel and its extensions
+pub fn wired_by_processes() -> u64 {
+    all_pids().into_iter()
+        .filter(|pid| *pid > 0)
+        .filter_map(|pid| rusage(pid as u32))
+        .map(|r| r.ri_wired_size)
+        .sum()
+}
+
+// Open descriptor table for a process. Only the (fd, type) pairs are fetched,
+// which is far cheaper than resolving each descriptor's path.
+fn list_fds(pid: u32) -> Option<Vec<libc::proc_fdinfo>> {
+    let pid = pid as libc::c_int;
+    // With a null buffer the kernel returns the size needed
+    let size = unsafe { libc::proc_pidinfo(pid, libc::PROC_PIDLISTFDS, 0, std::ptr::null_mut(), 0) };
+    if size <= 0 { return None; }
+
+    let cap = size as usize / std::mem::size_of::<libc::proc_fdinfo>();
+    let mut fds: Vec<libc::proc_fdinfo> = Vec::with_capacity(cap);
+    let written = unsafe {
+        libc::proc_pidinfo(
+            pid,
+            libc::PROC_PIDLISTFDS,
+            0,
+            fds.as_mut_ptr() as *mut libc::c_void,
+            (cap * std::mem::size_of::<libc::proc_fdinfo>()) as libc::c_int,
+        )
+    };
+    if written < 0 { return None; }
+    unsafe { fds.set_len(written as usize / std::mem::size_of::<libc::proc_fdinfo>()) };
+    Some(fds)
+}
+
+type CFTypeRef = *const libc::c_void;
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOServiceMatching(name: *const libc::c_char) -> CFTypeRef;
+    fn IOServiceGetMatchingServices(main_port: libc::mach_port_t, matching: CFTypeRef, iterator: *mut libc::mach_port_t) -> libc::kern_return_t;
+    fn IOIteratorNext(iterator: libc::mach_port_t) -> libc::mach_port_t;
+    fn IOObjectRelease(object: libc::mach_port_t) -> libc::kern_return_t;
+    fn IORegistryEntryCreateCFProperty(entry: libc::mach_port_t, key: CFTypeRef, allocator: CFTypeRef, options: u32) -> CFTypeRef;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    fn CFStringCreateWithCString(allocator: CFTypeRef, c_str: *const libc::c_char, encoding: u32) -> CFTypeRef;
+    fn CFStringGetCString(string: CFTypeRef, buffer: *mut libc::c_char, size: isize, encoding: u32) -> u8;
+    fn CFStringGetTypeID() -> usize;
+    fn CFArrayGetCount(array: CFTypeRef) -> isize;
+    fn CFArrayGetValueAtIndex(array: CFTypeRef, index: isize) -> CFTypeRef;
+    fn CFArrayGetTypeID() -> usize;
+    fn CFDictionaryGetValue(dict: CFTypeRef, key: CFTypeRef) -> CFTypeRef;
+    fn CFDictionaryGetTypeID() -> usize;
+    fn CFNumberGetValue(number: CFTypeRef, number_type: isize, value: *mut libc::c_void) -> u8;
+    fn CFNumberGetTypeID() -> usize;
+    fn CFGetTypeID(cf: CFTypeRef) -> usize;
+    fn CFRelease(cf: CFTypeRef);
+}
+
+const CF_STRING_ENCODING_UTF8: u32 = 0x0800_0100;
+const CF_NUMBER_SINT64: isize = 4;
+
+// A CF object we own a reference to (Create rule); null when the call failed
+struct CfOwned(CFTypeRef);
+
+impl Drop for CfOwned {
+    fn drop(&mut self) {
+        if !self.0.is_null() {
+            unsafe { CFRelease(self.0) };
+        }
+    }
+}
+
+fn cf_string(s: &std::ffi::CStr) -> CfOwned {
+    CfOwned(unsafe { CFStringCreateWithCString(std::ptr::null(), s.as_ptr(), CF_STRING_ENCODING_UTF8) })
+}
+
+fn cf_is(cf: CFTypeRef, type_id: usize) -> bool {
+    !cf.is_null() && unsafe { CFGetTypeID(cf) } == type_id
+}
+
+fn cf_to_string(cf: CFTypeRef) -> Option<String> {
+    if !cf_is(cf, unsafe { CFStringGetTypeID() }) { return None; }
+    let mut buf = [0 as libc::c_char; 256];
+    if unsafe { CFStringGetCString(cf, buf.as_mut_ptr(), buf.len() as isize, CF_STRING_ENCODING_UTF8) } == 0 {
+        return None;
+    }
+    Some(unsafe { std::ffi::CStr::from_ptr(buf.as_ptr()) }.to_string_lossy().to_string())
+}
+
+fn cf_to_u64(cf: CFTypeRef) -> Option<u64> {
+    if !cf_is(cf, unsafe { CFNumberGetTypeID() }) { return None; }
+    let mut value = 0i64;
+    let ok = unsafe { CFNumberGetValue(cf, CF_NUMBER_SINT64, &mut value as *mut i64 as *mut libc::c_void) };
+    (ok != 0).then_some(value.max(0) as u64)
+}
+
+// Items of a CFArray, borrowed from it (Get rule)
+fn cf_array_items(cf: CFTypeRef) -> Vec<CFTypeRef> {
+    if !cf_is(cf, unsafe { CFArrayGetTypeID() }) { return Vec::new(); }
+    (0..unsafe { CFArrayGetCount(cf) }).map(|i| unsafe { CFArrayGetValueAtIndex(cf, i) }).collect()
+}
+
+fn cf_dict_get(cf: CFTypeRef, key: CFTypeRef) -> CFTypeRef {
+    if !cf_is(cf, unsafe { CFDictionaryGetTypeID() }) { return std::ptr::null(); }
+    unsafe { CFDictionaryGetValue(cf, key) }
+}
+
+// IOUserClientCreator reads "pid 412, WindowServer"
+fn parse_client_creator(creator: &str) -> Option<u32> {
+    creator.strip_prefix("pid ")?.split(',').next()?.trim().parse().ok()
+}
+
+// Per-pid accumulated GPU time from the AGX driver's user clients, the same
+// counters Activity Monitor's GPU column is built on. Read straight from the
+// IORegistry, which is cheap enough to do on every poll; proc_pid_rusage has
+// no GPU counter. Empty on Intel Macs.
+fn read_gpu_times() -> HashMap<u32, u64> {
+    let mut times = HashMap::new();
+    // Consumed by IOServiceGetMatchingServices; port 0 is kIOMainPortDefault
+    let matching = unsafe { IOServiceMatching(c"AGXDeviceUserClient".as_ptr()) };
+    let mut iterator: libc::mach_port_t = 0;
+    if matching.is_null() || unsafe { IOServiceGetMatchingServices(0, matching, &mut iterator) } != 0 {
+        return times;
+    }
+    let creator_key = cf_string(c"IOUserClientCreator");
+    let usage_key = cf_string(c"AppUsage");
+    let gpu_time_key = cf_string(c"accumulatedGPUTime");
+
+    loop {
+        let client = unsafe { IOIteratorNext(iterator) };
+        if client == 0 { break; }
+        let creator = CfOwned(unsafe { IORegistryEntryCreateCFProperty(client, creator_key.0, std::ptr::null(), 0) });
+        let usage = CfOwned(unsafe { IORegistryEntryCreateCFProperty(client, usage_key.0, std::ptr::null(), 0) });
+        unsafe { IOObjectRelease(client) };
+
+        let Some(pid) = cf_to_string(creator.0).as_deref().and_then(parse_client_creator) else { continue };
+        let gpu_ns: u64 = cf_array_items(usage.0).into_iter()
+            .filter_map(|u| cf_to_u64(cf_dict_get(u, gpu_time_key.0)))
+            .sum();
+        *times.entry(pid).or_insert(0) += gpu_ns;
+    }
+    unsafe { IOObjectRelease(iterator) };
+    times
+}
+
+// GPU utilization per pid since the previous call, as a percentage of wall time
+fn sample_gpu_percent() -> HashMap<u32, f32> {
+    let now_times = read_gpu_times();
+    let mut last = LAST_GPU.lock().unwrap_or_else(|e| e.into_inner());
+    let elapsed_ns = last.1.elapsed().as_nanos() as f64;
+
+    let percents = now_times.iter()
+        .filter_map(|(pid, ns)| {
+            let prev = last.0.get(pid)?;
+            let delta = ns.saturating_sub(*prev) as f64;
+            if elapsed_ns <= 0.0 { return None; }
+            Some((*pid, ((delta / elapsed_ns * 100.0) as f32).min(100.0)))
+        })
+        .collect();
+
+    *last = (now_times, Instant::now());
+    percents
+}
+
+#[repr(C)]
+struct MachTimebaseInfo {
+    numer: u32,
+    denom: u32,
+}
+
+extern "C" {
+    fn mach_timebase_info(info: *mut MachTimebaseInfo) -> libc::kern_return_t;
+}
+
+// rusage times are Mach absolute time units, which aren't nanoseconds on Apple Silicon
+static TIMEBASE: std::sync::LazyLock<(u64, u64)> = std::sync::LazyLock::new(|| {
+    let mut info = MachTimebaseInfo { numer: 1, denom: 1 };
+    if unsafe { mach_timebase_info(&mut info) } != 0 || info.denom == 0 {
+        return (1, 1);
+    }
+    (info.numer as u64, info.denom as u64)
+});
+
+pub fn mach_time_to_ns(t: u64) -> u64 {
+    let (numer, denom) = *TIMEBASE;
+    t * numer / denom
+}
+
+#[derive(Clone, Copy)]
+struct RusageCounters {
+    wakeups: u64,
+    user_ns: u64,
+    system_ns: u64,
+}
+
+struct RusageRates {
+    wakeups_per_sec: f64,
+    cpu_user: f32,
+    cpu_system: f32,
+}
+
+// Rates per pid since the previous call. Waking the CPU often costs battery
+// even at low CPU usage; it's what Energy Impact weighs.
+fn sample_rusage(pids: impl Iterator<Item = u32>) -> HashMap<u32, RusageRates> {
+    let now_counts: HashMap<u32, RusageCounters> = pids
+        .filter_map(|pid| rusage(pid).map(|r| (pid, RusageCounters {
+            wakeups: r.ri_interrupt_wkups + r.ri_pkg_idle_wkups,
+            user_ns: mach_time_to_ns(r.ri_user_time),
+            system_ns: mach_time_to_ns(r.ri_system_time),
+        })))
+        .collect();
+    let mut last = LAST_RUSAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let elapsed = last.1.elapsed().as_secs_f64();
+
+    let percent = |now: u64, prev: u64| {
+        ((now.saturating_sub(prev) as f64 / (elapsed * 1e9) * 1000.0).round() / 10.0) as f32
+    };
+    let rates = now_counts.iter()
+        .filter_map(|(pid, now)| {
+            let prev = last.0.get(pid)?;
+            if elapsed <= 0.0 { return None; }
+            Some((*pid, RusageRates {
+                wakeups_per_sec: (now.wakeups.saturating_sub(prev.wakeups) as f64 / elapsed * 10.0).round() / 10.0,
+                cpu_user: percent(now.user_ns, prev.user_ns),
+                cpu_system: percent(now.system_ns, prev.system_ns),
+            }))
+        })
+        .collect();
+
+    *last = (now_counts, Instant::now());
+    rates
+}
+
+fn read_scheduling_policy(pid: u32) -> &'static str {
+    // Mach policy constants: POLICY_TIMESHARE = 1, POLICY_RR = 2, POLICY_FIFO = 4
+    match task_info(pid).map(|t| t.pti_policy) {
+        Some(1) => "timeshare",
+        Some(2) => "round-robin",
+        Some(4) => "fifo",
+        _ => "unknown",
+    }
+}
+
+fn bundle_info(exe: &str) -> BundleInfo {
+    if exe.is_empty() { return BundleInfo::default(); }
+    if let Some(cached) = BUNDLES.lock().unwrap_or_else(|e| e.into_inner()).get(exe) {
+        return cached.clone();
+    }
+    let info = read_bundle_info(exe);
+    BUNDLES.lock().unwrap_or_else(|e| e.into_inner()).insert(exe.to_string(), info.clone());
+    info
+}
+
+fn read_bundle_info(exe: &str) -> BundleInfo {
+    // Outermost .app: "Code Helper (Renderer).app" lives inside "Visual Studio Code.app"
+    let Some(app) = std::path::Path::new(exe)
+        .ancestors()
+        .filter(|p| p.extension().map(|e| e == "app").unwrap_or(false))
+        .last()
+    else {
+        return BundleInfo::default();
+    };
+
+    let plist = plist::Value::from_file(app.join("Contents/Info.plist")).ok();
+    let dict = plist.as_ref().and_then(|v| v.as_dictionary());
+    let get = |key: &str| dict.and_then(|d| d.get(key)).and_then(|v| v.as_string()).map(|s| s.to_string());
+    // Older bundles write these as the string "1" rather than a boolean
+    let flag = |key: &str| match dict.and_then(|d| d.get(key)) {
+        Some(plist::Value::Boolean(b)) => *b,
+        Some(plist::Value::String(s)) => s == "1" || s.eq_ignore_ascii_case("yes") || s.eq_ignore_ascii_case("true"),
+        Some(plist::Value::Integer(i)) => i.as_signed() == Some(1),
+        _ => false,
+    };
+    let activation_policy = if flag("LSBackgroundOnly") {
+        "prohibited"
+    } else if flag("LSUIElement") {
+        "accessory"
+    } else {
+        "regular"
+    };
+
+    BundleInfo {
+        bundle_id: get("CFBundleIdentifier"),
+        app_name: get("CFBundleDisplayName")
+            .or_else(|| get("CFBundleName"))
+            .or_else(|| app.file_stem().map(|s| s.to_string_lossy().to_string())),
+        activation_policy: Some(activation_policy),
+    }
+}
+
+fn status_name(status: ProcessStatus) -> &'static str {
+    match status {
+        ProcessStatus::Run => "running",
+        ProcessStatus::Sleep => "sleeping",
+        ProcessStatus::Idle => "idle",
+        ProcessStatus::Stop => "stopped",
+        ProcessStatus::Zombie => "zombie",
+        ProcessStatus::Dead => "dead",
+        ProcessStatus::Tracing => "tracing",
+        _ => "unknown",
+    }
+}
+
 fn format_elapsed(secs: u64) -> String {
     let hours = secs / 3600;
     let mins = (secs % 3600) / 60;
@@ -81,3 +1423,60 @@ fn format_elapsed(secs: u64) -> String {
         format!("{:02}:{:02}", mins, s)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_only_when_needed() {
+        assert_eq!(csv_field("/usr/sbin/cfprefsd"), "/usr/sbin/cfprefsd");
+        assert_eq!(csv_field("node server.js --port=3000,3001"), "\"node server.js --port=3000,3001\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+    }
+
+    #[test]
+    fn export_refuses_relative_paths_and_existing_files() {
+        assert!(matches!(export_processes("processes.csv", false), Err(AppError::InvalidArgument(_))));
+
+        let path = std::env::temp_dir().join(format!("mac-dash-export-{}.csv", std::process::id()));
+        std::fs::write(&path, "keep me").unwrap();
+        let path_str = path.to_str().unwrap();
+        assert!(matches!(export_processes(path_str, false), Err(AppError::InvalidArgument(_))));
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keep me");
+
+        assert!(export_processes(path_str, true).is_ok());
+        assert!(std::fs::read_to_string(&path).unwrap().starts_with(CSV_HEADER));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn parses_gpu_client_creator() {
+        assert_eq!(parse_client_creator("pid 412, WindowServer"), Some(412));
+        assert_eq!(parse_client_creator("pid 88213, Google Chrome Helper (GPU)"), Some(88213));
+        assert_eq!(parse_client_creator("WindowServer"), None);
+    }
+
+    #[test]
+    fn descendants_come_before_their_parents() {
+        let children: HashMap<u32, Vec<u32>> = HashMap::from([
+            (1, vec![100]),
+            (100, vec![200, 201]),
+            (200, vec![300]),
+        ]);
+        let order = descendants_bottom_up(100, &children);
+        assert_eq!(order.len(), 4);
+        let pos = |pid: u32| order.iter().position(|p| *p == pid).unwrap();
+        assert!(pos(300) < pos(200));
+        assert!(pos(200) < pos(100) && pos(201) < pos(100));
+        assert_eq!(order.last(), Some(&100));
+    }
+
+    #[test]
+    fn descendants_never_include_launchd() {
+        let children: HashMap<u32, Vec<u32>> = HashMap::from([(100, vec![1, 0, 200])]);
+        assert_eq!(descendants_bottom_up(100, &children), vec![200, 100]);
+        assert_eq!(descendants_bottom_up(300, &children), vec![300]);
+    }
+}
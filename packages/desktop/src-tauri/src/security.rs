@@ -0,0 +1,217 @@
+use serde::Serialize;
+use tokio::process::Command;
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityCheck {
+    pub enabled: Option<bool>, // None when the tool failed or its output wasn't recognized
+    pub detail: String,        // the tool's own output line
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SecurityStatus {
+    pub sip: SecurityCheck,
+    pub gatekeeper: SecurityCheck,
+    pub filevault: SecurityCheck,
+}
+
+// fdesetup in particular can stall while FileVault is converting
+const CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+// stdout and stderr together: spctl prints its status on stderr
+async fn run_check(args: &[&str]) -> Option<String> {
+    let output = Command::new(args[0])
+        .args(&args[1..])
+        .kill_on_drop(true)
+        .output();
+    let output = tokio::time::timeout(CHECK_TIMEOUT, output).await.ok()?.ok()?;
+    let mut text = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if text.is_empty() {
+        text = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    }
+    Some(text)
+}
+
+fn check(output: Option<String>, on: &str, off: &str) -> SecurityCheck {
+    let Some(text) = output else {
+        return SecurityCheck { enabled: None, detail: "check failed or timed out".into() };
+    };
+    let first = text.lines().next().unwrap_or_default().to_string();
+    let lower = first.to_lowercase();
+    let enabled = if lower.contains(off) {
+        Some(false)
+    } else if lower.contains(on) {
+        Some(true)
+    } else {
+        None
+    };
+    SecurityCheck { enabled, detail: first }
+}
+
+// None of these need root for a status read
+pub async fn get_security_status() -> SecurityStatus {
+    let (sip, gatekeeper, filevault) = tokio::join!(
+        // "System Integrity Protection status: enabled."
+        run_check(&["csrutil", "status"]),
+        // "assessments enabled"
+        run_check(&["spctl", "--status"]),
+        // "FileVault is On."
+        run_check(&["fdesetup", "status"]),
+    );
+    SecurityStatus {
+        sip: check(sip, "enabled", "disabled"),
+        gatekeeper: check(gatekeeper, "enabled", "disabled"),
+        filevault: check(filevault, "is on", "is off"),
+    }
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceUser {
+    pub pid: u32,
+    pub process: String,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PrivacyIndicators {
+    pub microphone: Vec<DeviceUser>, // empty before macOS 14, which added per-process audio objects
+    pub camera: Vec<DeviceUser>,     // often the system camera daemon rather than the app behind it
+}
+
+#[repr(C)]
+struct AudioObjectPropertyAddress {
+    selector: u32,
+    scope: u32,
+    element: u32,
+}
+
+#[link(name = "CoreAudio", kind = "framework")]
+extern "C" {
+    fn AudioObjectGetPropertyDataSize(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const libc::c_void,
+        out_size: *mut u32,
+    ) -> i32;
+    fn AudioObjectGetPropertyData(
+        object: u32,
+        address: *const AudioObjectPropertyAddress,
+        qualifier_size: u32,
+        qualifier: *const libc::c_void,
+        io_size: *mut u32,
+        out: *mut libc::c_void,
+    ) -> i32;
+}
+
+// CoreAudio selectors are four-char codes
+const AUDIO_SYSTEM_OBJECT: u32 = 1;
+const SCOPE_GLOBAL: u32 = u32::from_be_bytes(*b"glob");
+const PROCESS_OBJECT_LIST: u32 = u32::from_be_bytes(*b"prs#");
+const PROCESS_PID: u32 = u32::from_be_bytes(*b"ppid");
+const PROCESS_IS_RUNNING_INPUT: u32 = u32::from_be_bytes(*b"piri");
+
+fn audio_property<T: Copy + Default>(object: u32, selector: u32) -> Option<T> {
+    let address = AudioObjectPropertyAddress { selector, scope: SCOPE_GLOBAL, element: 0 };
+    let mut value = T::default();
+    let mut size = std::mem::size_of::<T>() as u32;
+    let status = unsafe {
+        AudioObjectGetPropertyData(object, &address, 0, std::ptr::null(), &mut size, &mut value as *mut T as *mut libc::c_void)
+    };
+    (status == 0).then_some(value)
+}
+
+// Processes with audio input running, from CoreAudio's per-process objects
+fn microphone_pids() -> Vec<u32> {
+    let address = AudioObjectPropertyAddress { selector: PROCESS_OBJECT_LIST, scope: SCOPE_GLOBAL, element: 0 };
+    let mut size = 0u32;
+    if unsafe { AudioObjectGetPropertyDataSize(AUDIO_SYSTEM_OBJECT, &address, 0, std::ptr::null(), &mut size) } != 0 {
+        return Vec::new();
+    }
+    let mut objects = vec![0u32; size as usize / std::mem::size_of::<u32>()];
+    let status = unsafe {
+        AudioObjectGetPropertyData(
+            AUDIO_SYSTEM_OBJECT, &address, 0, std::ptr::null(), &mut size, objects.as_mut_ptr() as *mut libc::c_void,
+        )
+    };
+    if status != 0 { return Vec::new(); }
+    objects.truncate(size as usize / std::mem::size_of::<u32>());
+
+    objects.into_iter()
+        .filter(|obj| audio_property::<u32>(*obj, PROCESS_IS_RUNNING_INPUT).unwrap_or(0) != 0)
+        .filter_map(|obj| audio_property::<i32>(obj, PROCESS_PID))
+        .filter(|pid| *pid > 0)
+        .map(|pid| pid as u32)
+        .collect()
+}
+
+// User client classes of the built-in camera drivers: ISP-based on Apple
+// silicon, AppleCamera on Intel FaceTime HD, and the UVC driver for USB cameras
+const CAMERA_CLIENT_CLASSES: &[&str] = &[
+    "AppleH13CamInUserClient",
+    "AppleH10CamInUserClient",
+    "AppleH9CamInUserClient",
+    "AppleCameraInterfaceUserClient",
+    "AppleUSBVideoSupportUserClient",
+];
+
+// Camera drivers hand out a user client per opener; ioreg names the creator as
+// `"IOUserClientCreator" = "pid 412, appleh13camerad"`
+fn parse_camera_clients(output: &str) -> Vec<DeviceUser> {
+    let mut users = Vec::new();
+    let mut in_camera_client = false;
+    for line in output.lines() {
+        if let Some(i) = line.find("+-o ") {
+            let class = line[i + 4..].split_whitespace().next().unwrap_or_default();
+            in_camera_client = CAMERA_CLIENT_CLASSES.contains(&class);
+            continue;
+        }
+        if !in_camera_client { continue; }
+        let Some(creator) = line.split("\"IOUserClientCreator\" = \"pid ").nth(1) else { continue };
+        let Some((pid, name)) = creator.trim_end_matches('"').split_once(", ") else { continue };
+        let Ok(pid) = pid.parse() else { continue };
+        if !users.iter().any(|u: &DeviceUser| u.pid == pid) {
+            users.push(DeviceUser { pid, process: name.to_string() });
+        }
+    }
+    users
+}
+
+pub async fn get_privacy_indicators() -> PrivacyIndicators {
+    let ioreg = run_check(&["ioreg", "-l", "-w0", "-r", "-c", "IOUserClient"]).await.unwrap_or_default();
+    let microphone = microphone_pids().into_iter()
+        .map(|pid| DeviceUser {
+            pid,
+            process: crate::processes::process_name(pid).unwrap_or_default(),
+        })
+        .collect();
+    PrivacyIndicators { microphone, camera: parse_camera_clients(&ioreg) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn camera_clients_match_exact_classes() {
+        let output = r#"+-o AppleH13CamInUserClient  <class AppleH13CamInUserClient, id 0x100000a1c>
+    {
+      "IOUserClientCreator" = "pid 412, appleh13camerad"
+    }
++-o AppleCamInCalibrationClient  <class IOUserClient, id 0x100000a1d>
+    {
+      "IOUserClientCreator" = "pid 97, bluetoothd"
+    }
++-o AppleH13CamInUserClient  <class AppleH13CamInUserClient, id 0x100000a1e>
+    {
+      "IOUserClientCreator" = "pid 412, appleh13camerad"
+    }
+"#;
+        let users = parse_camera_clients(output);
+        assert_eq!(users.len(), 1);
+        assert_eq!(users[0].pid, 412);
+        assert_eq!(users[0].process, "appleh13camerad");
+    }
+}
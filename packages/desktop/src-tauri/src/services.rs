@@ -1,21 +1,30 @@
 use serde::Serialize;
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::process::Command;
 
+use crate::error::{AppError, AppResult};
+use crate::timings::{self, Stage};
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct ServiceInfo {
     pub label: String,
     pub pid: Option<i32>,
     pub last_exit_status: Option<i32>,
-    pub status: String, // "running" | "stopped" | "error" | "unknown"
-    pub category: String,
+    pub uptime_seconds: Option<u64>, // None unless there's a live pid
+    pub status: String, // "running" | "stopped" | "waiting" | "error" | "unknown"
+    pub raw_state: Option<String>, // launchd's own `state`, for loaded services that aren't running
+    pub category: String, // from the plist location; "unknown" without a plist
     pub plist_path: Option<String>,
     pub program: Option<String>,
     pub program_arguments: Option<Vec<String>>,
     pub run_at_load: Option<bool>,
     pub enabled: bool,
+    pub is_favorite: bool,
+    pub flapping: bool, // restarting faster than the configured threshold
+    pub mdm_managed: bool, // pinned by a configuration profile; stopping or disabling won't stick
+    pub listening_ports: Vec<u16>, // empty unless running
 }
 
 #[derive(Serialize, Clone)]
@@ -29,6 +38,57 @@ pub struct ServiceDetail {
     pub last_exit_reason: Option<String>,
 }
 
+#[derive(Serialize, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+pub enum StopOutcome {
+    NotRunning,
+    Graceful,
+    Forced,
+    StillRunning,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedStop {
+    pub stopped: bool, // launchd no longer reports a pid for the label
+    pub pid: Option<i32>, // what launchd reports now; differs from the old pid after a KeepAlive relaunch
+    pub waited_ms: u64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct PlistEdit {
+    pub path: String,
+    pub writable: bool, // false for system/global plists; saving needs root
+}
+
+// How long a service gets to exit after SIGTERM before we consider it stuck
+const STOP_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct FlappingService {
+    pub label: String,
+    pub restarts_last_minute: usize,
+}
+
+pub const FLAPPING_EVENT: &str = "service-flapping";
+
+// How often the detector polls `launchctl list` for pid changes; launchd
+// throttles respawns to 10s by default, so this catches each restart
+const FLAP_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+const FLAP_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[derive(Default)]
+struct FlapTracker {
+    last_pid: HashMap<String, i32>,
+    restarts: HashMap<String, std::collections::VecDeque<std::time::Instant>>, // within FLAP_WINDOW
+    flapping: std::collections::HashSet<String>,
+}
+
+static FLAPS: std::sync::LazyLock<std::sync::Mutex<FlapTracker>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(FlapTracker::default()));
+
 struct LoadedService {
     pid: Option<i32>,
     exit_status: Option<i32>,
@@ -60,16 +120,139 @@ async fn get_loaded_services() -> HashMap<String, LoadedService> {
     map
 }
 
-#[allow(dead_code)]
+fn is_flapping(label: &str) -> bool {
+    FLAPS.lock().unwrap_or_else(|e| e.into_inner()).flapping.contains(label)
+}
+
+// A new pid under the same label is a restart. Returns the services that just
+// crossed the threshold, so each crash loop is reported once until it settles.
+fn record_pids(loaded: &HashMap<String, LoadedService>, threshold: usize) -> Vec<FlappingService> {
+    let now = std::time::Instant::now();
+    let mut tracker = FLAPS.lock().unwrap_or_else(|e| e.into_inner());
+    let FlapTracker { last_pid, restarts, flapping } = &mut *tracker;
+
+    for (label, service) in loaded {
+        let Some(pid) = service.pid.filter(|p| *p > 0) else { continue };
+        if let Some(prev) = last_pid.insert(label.clone(), pid) {
+            if prev != pid {
+                restarts.entry(label.clone()).or_default().push_back(now);
+            }
+        }
+    }
+    last_pid.retain(|label, _| loaded.contains_key(label));
+
+    let mut newly_flapping = Vec::new();
+    restarts.retain(|label, times| {
+        while times.front().map(|t| now.duration_since(*t) > FLAP_WINDOW).unwrap_or(false) {
+            times.pop_front();
+        }
+        if times.len() > threshold {
+            if flapping.insert(label.clone()) {
+                newly_flapping.push(FlappingService { label: label.clone(), restarts_last_minute: times.len() });
+            }
+        } else {
+            flapping.remove(label);
+        }
+        !times.is_empty()
+    });
+    flapping.retain(|label| restarts.contains_key(label));
+    newly_flapping
+}
+
+pub fn start_flap_detector(app: tauri::AppHandle) {
+    use tauri::Emitter;
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let loaded = get_loaded_services().await;
+            let threshold = crate::config::get().flapping_threshold as usize;
+            for service in record_pids(&loaded, threshold) {
+                let _ = app.emit(FLAPPING_EVENT, &service);
+            }
+            tokio::time::sleep(FLAP_POLL_INTERVAL).await;
+        }
+    });
+}
+
+pub fn set_flapping_threshold(restarts_per_minute: u32) -> AppResult<u32> {
+    let threshold = restarts_per_minute.max(1);
+    crate::config::update(|c| {
+        c.flapping_threshold = threshold;
+        Ok(())
+    })?;
+    Ok(threshold)
+}
+
+// What `launchctl print` tells us beyond `launchctl list`
+#[derive(Default)]
+struct PrintInfo {
+    state: Option<String>,
+    path: Option<String>, // the plist launchd loaded the job from
+}
+
+fn parse_print(output: &str) -> PrintInfo {
+    // The service's own fields come before any nested endpoint ones
+    let field = |key: &str| output.lines()
+        .find_map(|l| l.trim().strip_prefix(key))
+        .map(|s| s.to_string());
+    PrintInfo { state: field("state = "), path: field("path = ") }
+}
+
+// How many `launchctl print` calls fetch_print_info keeps in flight at once
+const PRINT_CONCURRENCY: usize = 8;
+
+// Asks launchd about each label; only worth doing for loaded services without
+// a pid, where `launchctl list` alone is ambiguous, or without a known plist
+async fn fetch_print_info(labels: Vec<String>) -> HashMap<String, PrintInfo> {
+    let uid = unsafe { libc::getuid() };
+    let permits = std::sync::Arc::new(tokio::sync::Semaphore::new(PRINT_CONCURRENCY));
+    let mut set = tokio::task::JoinSet::new();
+    for label in labels {
+        let permits = permits.clone();
+        set.spawn(async move {
+            let _permit = permits.acquire_owned().await;
+            let output = exec_cmd(&["launchctl", "print", &format!("gui/{}/{}", uid, label)]).await;
+            (label, parse_print(&output))
+        });
+    }
+
+    let mut info = HashMap::new();
+    while let Some(res) = set.join_next().await {
+        if let Ok((label, print)) = res {
+            info.insert(label, print);
+        }
+    }
+    info
+}
+
+// The plist path launchd reports for a loaded job, if it's still on disk
+fn loaded_plist_path(print: Option<&PrintInfo>) -> Option<String> {
+    print?.path.clone()
+        .filter(|p| p.ends_with(".plist") && std::path::Path::new(p).is_file())
+}
+
+fn derive_status(loaded: Option<&LoadedService>, raw_state: Option<&str>) -> &'static str {
+    match (loaded, raw_state) {
+        (Some(l), _) if l.pid.map(|p| p > 0).unwrap_or(false) => "running",
+        (Some(l), _) if l.exit_status.map(|e| e != 0).unwrap_or(false) => "error",
+        (Some(_), Some("running")) => "running",
+        (Some(_), Some("waiting")) => "waiting",
+        (Some(_), Some(s)) if s.contains("spawn failed") || s.contains("penalty") => "error",
+        _ => "stopped",
+    }
+}
+
+// Categorize a service by where its plist lives. Services loaded without a
+// plist on disk (e.g. XPC services embedded in bundles) are "unknown".
 fn categorize_path(path: &str) -> &'static str {
-    if path.contains("/Library/LaunchAgents") {
-        if path.starts_with("/System") { "system-agents" }
-        else if path.starts_with("/Library") { "global-agents" }
-        else { "user-agents" }
-    } else if path.contains("/Library/LaunchDaemons") {
-        if path.starts_with("/System") { "system-daemons" }
-        else { "global-daemons" }
+    let is_daemon = path.contains("/LaunchDaemons/");
+    if path.starts_with("/System/Library/") {
+        if is_daemon { "system-daemons" } else { "system-agents" }
+    } else if path.starts_with("/Library/") || is_daemon {
+        // Daemons outside /Library (e.g. Homebrew's) still run system-wide
+        if is_daemon { "global-daemons" } else { "global-agents" }
     } else {
+        // ~/Library/LaunchAgents — user daemons don't exist
         "user-agents"
     }
 }
@@ -97,88 +280,216 @@ fn read_plist_info(path: &str) -> (Option<String>, Option<Vec<String>>, Option<b
     (prog, args, run_at_load)
 }
 
+fn service_uptime(pid: i32) -> Option<u64> {
+    if pid <= 0 { return None; }
+    crate::processes::uptime_secs(pid as u32)
+}
+
+// Apple Silicon and Intel install locations. Formulae ship their service
+// plist in the keg; `brew services start` copies it to ~/Library/LaunchAgents
+// (or /Library/LaunchDaemons under sudo).
+const HOMEBREW_PREFIXES: [&str; 2] = ["/opt/homebrew", "/usr/local"];
+
 async fn discover_plists() -> Vec<(String, String, String)> {
     // (label, path, category)
     let home = dirs::home_dir().unwrap_or_default();
-    let dirs_to_scan: Vec<(PathBuf, &str)> = vec![
-        (home.join("Library/LaunchAgents"), "user-agents"),
-        (PathBuf::from("/Library/LaunchAgents"), "global-agents"),
-        (PathBuf::from("/Library/LaunchDaemons"), "global-daemons"),
-        (PathBuf::from("/System/Library/LaunchAgents"), "system-agents"),
-        (PathBuf::from("/System/Library/LaunchDaemons"), "system-daemons"),
+    let mut dirs_to_scan: Vec<PathBuf> = vec![
+        home.join("Library/LaunchAgents"),
+        PathBuf::from("/Library/LaunchAgents"),
+        PathBuf::from("/Library/LaunchDaemons"),
+        PathBuf::from("/System/Library/LaunchAgents"),
+        PathBuf::from("/System/Library/LaunchDaemons"),
     ];
+    for dir in crate::config::get().extra_service_dirs {
+        let dir = match dir.strip_prefix("~/") {
+            Some(rest) => home.join(rest),
+            None => PathBuf::from(dir),
+        };
+        // Listing a standard dir again would duplicate every label in it
+        if !dirs_to_scan.contains(&dir) {
+            dirs_to_scan.push(dir);
+        }
+    }
 
     let mut results = Vec::new();
-    for (dir, category) in dirs_to_scan {
+    for dir in dirs_to_scan {
         if let Ok(entries) = std::fs::read_dir(&dir) {
             for entry in entries.flatten() {
                 let name = entry.file_name().to_string_lossy().to_string();
                 if name.ends_with(".plist") || name.ends_with(".plist.disabled") {
                     let label = name.trim_end_matches(".disabled").trim_end_matches(".plist").to_string();
                     let path = entry.path().to_string_lossy().to_string();
-                    results.push((label, path, category.to_string()));
+                    let category = categorize_path(&path).to_string();
+                    results.push((label, path, category));
                 }
             }
         }
     }
+
+    // Formula services that were never started; started ones were found above
+    for prefix in HOMEBREW_PREFIXES {
+        let Ok(paths) = glob::glob(&format!("{}/opt/*/homebrew.mxcl.*.plist", prefix)) else { continue };
+        for path in paths.flatten() {
+            let Some(label) = path.file_stem().map(|s| s.to_string_lossy().to_string()) else { continue };
+            if results.iter().any(|(l, _, _)| *l == label) { continue; }
+            let path = path.to_string_lossy().to_string();
+            let category = categorize_path(&path).to_string();
+            results.push((label, path, category));
+        }
+    }
     results
 }
 
+const MANAGED_PREFS_DIR: &str = "/Library/Managed Preferences";
+
+// Label rules from the Managed Login Items payload (macOS 13+), which MDM uses
+// to keep its agents and daemons loaded
+enum ManagedRule {
+    Label(String),
+    LabelPrefix(String),
+}
+
+fn managed_rules() -> Vec<ManagedRule> {
+    let path = Path::new(MANAGED_PREFS_DIR).join("com.apple.servicemanagement.plist");
+    let Ok(val) = plist::Value::from_file(path) else { return Vec::new() };
+    let rules = val.as_dictionary()
+        .and_then(|d| d.get("Rules"))
+        .and_then(|v| v.as_array())
+        .map(|a| a.as_slice())
+        .unwrap_or_default();
+    rules.iter().filter_map(|rule| {
+        let rule = rule.as_dictionary()?;
+        let value = rule.get("RuleValue")?.as_string()?.to_string();
+        match rule.get("RuleType")?.as_string()? {
+            "Label" => Some(ManagedRule::Label(value)),
+            "LabelPrefix" => Some(ManagedRule::LabelPrefix(value)),
+            _ => None, // team/bundle id rules would need the binary's signature
+        }
+    }).collect()
+}
+
+fn is_mdm_managed(label: &str, plist_path: Option<&str>, rules: &[ManagedRule]) -> bool {
+    plist_path.map(|p| p.starts_with(MANAGED_PREFS_DIR)).unwrap_or(false)
+        || rules.iter().any(|rule| match rule {
+            ManagedRule::Label(l) => l == label,
+            ManagedRule::LabelPrefix(prefix) => label.starts_with(prefix.as_str()),
+        })
+}
+
+fn build_service(
+    label: &str,
+    plist: Option<(&str, &str)>, // (path, category)
+    loaded: Option<&LoadedService>,
+    raw_state: Option<String>,
+    favorites: &[String],
+    managed: &[ManagedRule],
+    listening: &HashMap<i32, Vec<u16>>,
+) -> ServiceInfo {
+    let status = derive_status(loaded, raw_state.as_deref());
+    let (program, program_arguments, run_at_load) = match plist {
+        Some((path, _)) => read_plist_info(path),
+        None => (None, None, None),
+    };
+    ServiceInfo {
+        label: label.to_string(),
+        pid: loaded.and_then(|l| l.pid),
+        last_exit_status: loaded.and_then(|l| l.exit_status),
+        uptime_seconds: loaded.and_then(|l| l.pid).and_then(service_uptime),
+        status: status.into(),
+        raw_state,
+        category: match plist {
+            Some((_, category)) => category.to_string(),
+            None => "unknown".into(),
+        },
+        plist_path: plist.map(|(path, _)| path.to_string()),
+        program,
+        program_arguments,
+        run_at_load,
+        enabled: match plist {
+            Some((path, _)) => !path.ends_with(".disabled") && loaded.is_some(),
+            None => true,
+        },
+        is_favorite: favorites.iter().any(|f| f == label),
+        flapping: is_flapping(label),
+        mdm_managed: is_mdm_managed(label, plist.map(|(path, _)| path), managed),
+        listening_ports: loaded.and_then(|l| l.pid)
+            .and_then(|pid| listening.get(&pid).cloned())
+            .unwrap_or_default(),
+    }
+}
+
+// `launchctl list <label>` prints just that job, e.g. `"PID" = 412;`;
+// nothing on stdout when it isn't loaded
+async fn get_loaded_service(label: &str) -> Option<LoadedService> {
+    let output = exec_cmd(&["launchctl", "list", label]).await;
+    if output.is_empty() { return None; }
+    let field = |key: &str| output.lines()
+        .find_map(|l| l.trim().strip_prefix(&format!("\"{}\" = ", key)))
+        .and_then(|v| v.trim_end_matches(';').parse().ok());
+    Some(LoadedService { pid: field("PID"), exit_status: field("LastExitStatus") })
+}
+
+// One fresh row, for updating the table after an action without a full rescan
+pub async fn get_service(label: &str) -> AppResult<ServiceInfo> {
+    let (loaded, plists) = tokio::join!(get_loaded_service(label), discover_plists());
+    let plist = plists.into_iter().find(|(l, _, _)| l == label);
+    if loaded.is_none() && plist.is_none() {
+        return Err(AppError::NotFound(format!("Service not found: {}", label)));
+    }
+    let print = match &loaded {
+        Some(l) if l.pid.is_none() || plist.is_none() => fetch_print_info(vec![label.to_string()]).await.remove(label),
+        _ => None,
+    };
+    let running: Vec<i32> = loaded.as_ref().and_then(|l| l.pid).into_iter().collect();
+    let listening = crate::network::listening_ports_by_pid(&running).await;
+    let plist = plist.map(|(_, path, category)| (path, category)).or_else(|| {
+        let path = loaded_plist_path(print.as_ref())?;
+        let category = categorize_path(&path).to_string();
+        Some((path, category))
+    });
+    let favorites = crate::config::get().favorites.services;
+    let plist = plist.as_ref().map(|(path, category)| (path.as_str(), category.as_str()));
+    Ok(build_service(label, plist, loaded.as_ref(), print.and_then(|p| p.state), &favorites, &managed_rules(), &listening))
+}
+
 pub async fn list_services() -> Vec<ServiceInfo> {
+    let started = std::time::Instant::now();
     let (loaded, plists) = tokio::join!(get_loaded_services(), discover_plists());
+    let seen: std::collections::HashSet<&str> = plists.iter().map(|(label, _, _)| label.as_str()).collect();
+    // Apple's jobs (mostly XPC services inside bundles) are skipped entirely;
+    // asking about each would cost hundreds of `launchctl print` calls for nothing
+    let to_print: Vec<String> = loaded.iter()
+        .filter(|(label, l)| {
+            !label.starts_with("com.apple.") && (l.pid.is_none() || !seen.contains(label.as_str()))
+        })
+        .map(|(label, _)| label.clone())
+        .collect();
+    let running: Vec<i32> = loaded.values().filter_map(|l| l.pid).filter(|p| *p > 0).collect();
+    let (prints, listening) = tokio::join!(
+        fetch_print_info(to_print),
+        crate::network::listening_ports_by_pid(&running),
+    );
+    let favorites = crate::config::get().favorites.services;
+    let state = |label: &str| prints.get(label).and_then(|p| p.state.clone());
+    let managed = managed_rules();
 
     let mut services = Vec::new();
-    let mut seen = std::collections::HashSet::new();
 
     for (label, path, category) in &plists {
-        seen.insert(label.clone());
-        let loaded_info = loaded.get(label);
-        let is_disabled = path.ends_with(".disabled");
-        let (program, args, run_at_load) = read_plist_info(path);
-
-        let status = match loaded_info {
-            Some(l) if l.pid.map(|p| p > 0).unwrap_or(false) => "running",
-            Some(l) if l.exit_status.map(|e| e != 0).unwrap_or(false) => "error",
-            Some(_) => "stopped",
-            None => "stopped",
-        };
-
-        services.push(ServiceInfo {
-            label: label.clone(),
-            pid: loaded_info.and_then(|l| l.pid),
-            last_exit_status: loaded_info.and_then(|l| l.exit_status),
-            status: status.into(),
-            category: category.clone(),
-            plist_path: Some(path.clone()),
-            program,
-            program_arguments: args,
-            run_at_load,
-            enabled: !is_disabled && loaded_info.is_some(),
-        });
+        services.push(build_service(label, Some((path, category)), loaded.get(label), state(label), &favorites, &managed, &listening));
     }
 
-    // Loaded services without plists
+    // Loaded services outside the scanned dirs; launchd may still know the plist
     for (label, info) in &loaded {
-        if seen.contains(label) { continue; }
-        let status = if info.pid.map(|p| p > 0).unwrap_or(false) { "running" }
-            else if info.exit_status.map(|e| e != 0).unwrap_or(false) { "error" }
-            else { "stopped" };
-
-        services.push(ServiceInfo {
-            label: label.clone(),
-            pid: info.pid,
-            last_exit_status: info.exit_status,
-            status: status.into(),
-            category: if label.starts_with("com.apple.") { "system-agents" } else { "user-agents" }.into(),
-            plist_path: None,
-            program: None,
-            program_arguments: None,
-            run_at_load: None,
-            enabled: true,
-        });
+        if seen.contains(label.as_str()) { continue; }
+        let plist = loaded_plist_path(prints.get(label))
+            .map(|path| { let category = categorize_path(&path); (path, category) });
+        let plist = plist.as_ref().map(|(path, category)| (path.as_str(), *category));
+        services.push(build_service(label, plist, Some(info), state(label), &favorites, &managed, &listening));
     }
 
     services.sort_by(|a, b| a.label.cmp(&b.label));
+    timings::record(Stage::ServiceDiscovery, started);
     services
 }
 
@@ -207,34 +518,128 @@ pub async fn get_service_detail(label: &str) -> Option<ServiceDetail> {
     Some(detail)
 }
 
-pub async fn start_service(label: &str) -> Result<(), String> {
+pub async fn start_service(label: &str) -> AppResult<()> {
     let uid = unsafe { libc::getuid() };
     let result = exec_cmd(&["launchctl", "kickstart", &format!("gui/{}/{}", uid, label)]).await;
     if result.contains("Could not find service") {
         let sys_result = exec_cmd(&["launchctl", "kickstart", &format!("system/{}", label)]).await;
         if sys_result.contains("Could not find service") {
-            return Err("Service not found".into());
+            return Err(AppError::NotFound(format!("Service not found: {}", label)));
         }
     }
     Ok(())
 }
 
-pub async fn stop_service(label: &str) -> Result<(), String> {
+fn pid_alive(pid: i32) -> bool {
+    // EPERM means it exists but belongs to someone else
+    let ret = unsafe { libc::kill(pid, 0) };
+    ret == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+async fn wait_for_exit(pid: i32, timeout: std::time::Duration) -> bool {
+    let deadline = std::time::Instant::now() + timeout;
+    while std::time::Instant::now() < deadline {
+        if !pid_alive(pid) { return true; }
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+    }
+    !pid_alive(pid)
+}
+
+pub async fn stop_service(label: &str, force: bool) -> AppResult<StopOutcome> {
     let uid = unsafe { libc::getuid() };
-    exec_cmd(&["launchctl", "kill", "SIGTERM", &format!("gui/{}/{}", uid, label)]).await;
-    Ok(())
+    let target = format!("gui/{}/{}", uid, label);
+    let pid = get_loaded_services().await
+        .get(label)
+        .and_then(|l| l.pid)
+        .filter(|p| *p > 0);
+
+    exec_cmd(&["launchctl", "kill", "SIGTERM", &target]).await;
+
+    let Some(pid) = pid else { return Ok(StopOutcome::NotRunning) };
+    // KeepAlive services get relaunched under a new pid; we only care that this one exits
+    if wait_for_exit(pid, STOP_GRACE).await {
+        return Ok(StopOutcome::Graceful);
+    }
+    if !force {
+        return Ok(StopOutcome::StillRunning);
+    }
+
+    exec_cmd(&["launchctl", "kill", "SIGKILL", &target]).await;
+    if wait_for_exit(pid, std::time::Duration::from_secs(1)).await {
+        Ok(StopOutcome::Forced)
+    } else {
+        Err(AppError::CommandFailed(format!("Process {} did not exit after SIGKILL", pid)))
+    }
+}
+
+// SIGTERM, then poll launchd itself rather than the old pid, so the answer is
+// what the services table will show on its next refresh
+pub async fn stop_service_and_wait(label: &str, timeout_secs: u64) -> AppResult<VerifiedStop> {
+    let uid = unsafe { libc::getuid() };
+    let started = std::time::Instant::now();
+    let running_pid = |l: Option<LoadedService>| l.and_then(|l| l.pid).filter(|p| *p > 0);
+    let loaded = get_loaded_service(label).await
+        .ok_or_else(|| AppError::NotFound(format!("Service not loaded: {}", label)))?;
+    let Some(original) = running_pid(Some(loaded)) else {
+        return Ok(VerifiedStop { stopped: true, pid: None, waited_ms: 0 });
+    };
+
+    let result = exec_cmd(&["launchctl", "kill", "SIGTERM", &format!("gui/{}/{}", uid, label)]).await;
+    if result.contains("Could not find service") {
+        exec_cmd(&["launchctl", "kill", "SIGTERM", &format!("system/{}", label)]).await;
+    }
+
+    let deadline = started + std::time::Duration::from_secs(timeout_secs.max(1));
+    let mut pid = Some(original);
+    while std::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(250)).await;
+        pid = running_pid(get_loaded_service(label).await);
+        if pid != Some(original) { break; }
+    }
+    Ok(VerifiedStop {
+        stopped: pid.is_none(),
+        pid,
+        waited_ms: started.elapsed().as_millis() as u64,
+    })
 }
 
-pub async fn enable_service(plist_path: &str) -> Result<(), String> {
+// The plist for a label, from the same scan list_services uses. The same
+// label in two directories is ambiguous, so the caller has to pick a path.
+pub async fn find_plist(label: &str) -> AppResult<String> {
+    let candidates: Vec<String> = discover_plists().await
+        .into_iter()
+        .filter(|(l, _, _)| l == label)
+        .map(|(_, path, _)| path)
+        .collect();
+    match candidates.as_slice() {
+        [] => {
+            // Loaded from somewhere we don't scan; launchd still knows where
+            let print = fetch_print_info(vec![label.to_string()]).await;
+            loaded_plist_path(print.get(label))
+                .ok_or_else(|| AppError::NotFound(format!("No plist on disk for {}", label)))
+        }
+        [path] => Ok(path.clone()),
+        _ => Err(AppError::InvalidArgument(format!(
+            "Multiple plists for {}: {}", label, candidates.join(", ")
+        ))),
+    }
+}
+
+pub async fn enable_service(plist_path: &str) -> AppResult<()> {
     let actual = plist_path.trim_end_matches(".disabled");
+    // launchctl reports a malformed plist only as a vague load failure
+    plist::Value::from_file(plist_path).map_err(|e| match e.into_io() {
+        Ok(io) => AppError::from(io),
+        Err(e) => AppError::Parse(format!("Invalid plist {}: {}", plist_path, e)),
+    })?;
     if plist_path.ends_with(".disabled") {
-        std::fs::rename(plist_path, actual).map_err(|e| e.to_string())?;
+        std::fs::rename(plist_path, actual)?;
     }
     exec_cmd(&["launchctl", "load", "-w", actual]).await;
     Ok(())
 }
 
-pub async fn disable_service(label: &str, plist_path: Option<&str>) -> Result<(), String> {
+pub async fn disable_service(label: &str, plist_path: Option<&str>) -> AppResult<()> {
     if let Some(path) = plist_path {
         exec_cmd(&["launchctl", "unload", "-w", path]).await;
     } else {
@@ -242,3 +647,142 @@ pub async fn disable_service(label: &str, plist_path: Option<&str>) -> Result<()
     }
     Ok(())
 }
+
+pub async fn edit_service_plist(label: &str) -> AppResult<PlistEdit> {
+    let path = find_plist(label).await?;
+
+    let c_path = std::ffi::CString::new(path.as_bytes())
+        .map_err(|e| AppError::InvalidArgument(e.to_string()))?;
+    let writable = unsafe { libc::access(c_path.as_ptr(), libc::W_OK) } == 0;
+
+    // `open -t` uses the default text editor; a configured editor is an app name
+    let mut cmd = Command::new("open");
+    match crate::config::get().editor {
+        Some(editor) => cmd.args(["-a", editor.as_str()]),
+        None => cmd.arg("-t"),
+    };
+    let output = cmd.arg(&path).output().await?;
+    if !output.status.success() {
+        return Err(AppError::from_stderr(&String::from_utf8_lossy(&output.stderr)));
+    }
+    Ok(PlistEdit { path, writable })
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceOutputLine {
+    pub label: String,
+    pub stream: String, // "stdout" | "stderr"
+    pub line: String,
+}
+
+pub const OUTPUT_EVENT: &str = "service-output";
+
+// Output follows keyed by label; sending on (or dropping) the sender stops
+// that follow's `tail` child
+static OUTPUT_FOLLOWS: std::sync::LazyLock<std::sync::Mutex<HashMap<String, tokio::sync::oneshot::Sender<()>>>> =
+    std::sync::LazyLock::new(|| std::sync::Mutex::new(HashMap::new()));
+
+// (path, stream) for the plist's StandardOutPath/StandardErrorPath; one entry
+// when both point at the same file
+fn output_paths(plist_path: &str) -> Vec<(String, &'static str)> {
+    let Ok(val) = plist::Value::from_file(plist_path) else { return Vec::new() };
+    let Some(dict) = val.as_dictionary() else { return Vec::new() };
+    let mut paths: Vec<(String, &'static str)> = Vec::new();
+    for (key, stream) in [("StandardOutPath", "stdout"), ("StandardErrorPath", "stderr")] {
+        if let Some(path) = dict.get(key).and_then(|v| v.as_string()) {
+            if !paths.iter().any(|(p, _)| p == path) {
+                paths.push((path.to_string(), stream));
+            }
+        }
+    }
+    paths
+}
+
+// Tails the service's output files and emits each new line. `tail -F` keeps
+// retrying files that don't exist yet and reopens them after log rotation.
+pub async fn follow_service_output(app: tauri::AppHandle, label: &str) -> AppResult<Vec<String>> {
+    use tauri::Emitter;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+
+    let plist_path = find_plist(label).await?;
+    let paths = output_paths(&plist_path);
+    if paths.is_empty() {
+        return Err(AppError::NotFound(format!("{} doesn't write its output to files", label)));
+    }
+    let followed: Vec<String> = paths.iter().map(|(p, _)| p.clone()).collect();
+
+    let (stop_tx, mut stop_rx) = tokio::sync::oneshot::channel();
+    {
+        let mut follows = OUTPUT_FOLLOWS.lock().unwrap_or_else(|e| e.into_inner());
+        if follows.contains_key(label) {
+            return Ok(followed); // already following
+        }
+        follows.insert(label.to_string(), stop_tx);
+    }
+
+    let mut child = match Command::new("tail")
+        .args(["-F", "-n", "0"])
+        .args(&followed)
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+    {
+        Ok(c) => c,
+        Err(e) => {
+            OUTPUT_FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(label);
+            return Err(AppError::from(e));
+        }
+    };
+
+    let label = label.to_string();
+    tauri::async_runtime::spawn(async move {
+        let stdout = child.stdout.take().unwrap();
+        let mut reader = BufReader::new(stdout).lines();
+        let mut stream = paths[0].1;
+        let mut stopped = false;
+
+        loop {
+            tokio::select! {
+                line = reader.next_line() => {
+                    let Ok(Some(line)) = line else { break };
+                    // With several files, tail marks each switch with "==> path <=="
+                    if let Some(path) = line.strip_prefix("==> ").and_then(|l| l.strip_suffix(" <==")) {
+                        if let Some((_, s)) = paths.iter().find(|(p, _)| p == path) {
+                            stream = s;
+                        }
+                        continue;
+                    }
+                    let _ = app.emit(OUTPUT_EVENT, ServiceOutputLine {
+                        label: label.clone(),
+                        stream: stream.into(),
+                        line,
+                    });
+                }
+                _ = &mut stop_rx => {
+                    stopped = true;
+                    break;
+                }
+            }
+        }
+
+        let _ = child.kill().await;
+        // An unfollow already removed our entry (and a re-follow may have replaced it)
+        if !stopped {
+            OUTPUT_FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(&label);
+        }
+    });
+    Ok(followed)
+}
+
+pub fn unfollow_service_output(label: &str) -> bool {
+    let stop = OUTPUT_FOLLOWS.lock().unwrap_or_else(|e| e.into_inner()).remove(label);
+    match stop {
+        Some(tx) => {
+            let _ = tx.send(());
+            true
+        }
+        None => false,
+    }
+}
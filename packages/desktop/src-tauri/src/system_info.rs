@@ -1,8 +1,11 @@
 use serde::Serialize;
-use sysinfo::{CpuRefreshKind, Disks, MemoryRefreshKind, RefreshKind, System};
+use sysinfo::{Disks, MemoryRefreshKind, System};
 use std::sync::Mutex;
 use std::time::Instant;
 
+use crate::error::AppResult;
+use crate::timings::{self, Stage};
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CpuStats {
@@ -12,6 +15,21 @@ pub struct CpuStats {
     pub model: String,
     pub cores: usize,
     pub load_avg: [f64; 3],
+    pub load_avg_normalized: [f64; 3], // load_avg / cores
+    pub ticks: Option<CpuTicks>,
+}
+
+// Cumulative scheduler ticks summed over all cores. The kernel's per-core
+// counters are u32 and wrap, so these are accumulated from per-core deltas
+// instead; they only ever grow, and clients can diff two samples over
+// whatever window they like.
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CpuTicks {
+    pub user: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub nice: u64,
 }
 
 #[derive(Serialize, Clone)]
@@ -20,11 +38,26 @@ pub struct MemoryStats {
     pub total: u64,
     pub used: u64,
     pub free: u64,
+    pub available: u64, // free + purgeable + file-backed cache, what memory_pressure counts
     pub wired: u64,
     pub compressed: u64,
+    pub active: u64,
+    pub inactive: u64,
+    pub purgeable: u64,
     pub used_percent: f64,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapStats {
+    pub total: u64,
+    pub used: u64,
+    pub swap_ins_per_sec: f64,  // pages/s since the previous sample
+    pub swap_outs_per_sec: f64,
+    pub swap_files: Vec<String>, // full paths, in creation order
+    pub swap_file_count: usize,
+}
+
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct DiskStats {
@@ -33,6 +66,10 @@ pub struct DiskStats {
     pub free: u64,
     pub used_percent: f64,
     pub mount_point: String,
+    pub read_only: bool,
+    pub mount_options: Vec<String>,
+    pub role: Option<String>, // APFS volume role: "System", "Data", "VM", ...; None off APFS
+    pub purgeable: Option<u64>, // bytes macOS frees on demand (snapshots, caches), on top of `free`
 }
 
 #[derive(Serialize, Clone)]
@@ -40,35 +77,393 @@ pub struct DiskStats {
 pub struct SystemStats {
     pub cpu: CpuStats,
     pub memory: MemoryStats,
+    pub swap: SwapStats,
     pub disk: DiskStats,
+    pub aggregate_disk: DiskStats,
     pub uptime: String,
     pub hostname: String,
     pub os_version: String,
+    pub os_build: Option<String>,
+    pub os_name: String,
     pub process_count: usize,
     pub thread_count: usize,
+    pub zombie_count: usize, // as of the last process list refresh
+    pub thermal_state: String, // "nominal" | "fair" | "serious" | "critical" | "unknown"
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TrendSample {
+    pub timestamp: i64, // unix millis
+    pub process_count: usize,
+    pub thread_count: usize,
+    pub load_avg_one: f64,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemTrends {
+    pub uptime_secs: u64,
+    pub samples: Vec<TrendSample>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CoreDetail {
+    pub index: usize,
+    pub cluster: Option<String>, // "performance" | "efficiency" on Apple Silicon
+    pub usage: f32,
+    pub frequency_mhz: Option<u64>,
 }
 
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct HardwareInfo {
     pub model: String,
+    pub model_identifier: Option<String>, // e.g. "Mac14,3"
+    pub model_name: Option<String>,       // e.g. "Mac mini (2023)"
     pub cpu: String,
     pub cores: usize,
     pub memory: u64,
     pub os_version: String,
+    pub os_build: Option<String>,
+    pub os_name: String,
     pub hostname: String,
     pub serial_number: Option<String>,
 }
 
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct DisplayState {
+    pub asleep: bool, // main display
+    pub locked: bool,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct WiredBreakdown {
+    pub total: u64,
+    pub kernel: u64,    // kernel, kexts and drivers: total minus what processes account for
+    pub processes: u64, // sum of per-process wired sizes (mlock'd, IOKit-mapped, ...)
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthFactor {
+    pub name: String,
+    pub penalty: f64, // points taken off the score
+}
+
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct HealthScore {
+    pub score: u8,                  // 0-100, higher is healthier
+    pub top_factor: Option<String>, // biggest penalty, None when nothing is wrong
+    pub factors: Vec<HealthFactor>,
+}
+
+// Bounds for the configurable minimum time between CPU/memory refreshes;
+// calls inside the interval reuse the previous sample
+pub const MIN_REFRESH_INTERVAL_MS: u64 = 100;
+pub const MAX_REFRESH_INTERVAL_MS: u64 = 10_000;
+
+static REFRESH_INTERVAL_MS: std::sync::LazyLock<std::sync::atomic::AtomicU64> =
+    std::sync::LazyLock::new(|| {
+        let ms = crate::config::get().stats_refresh_interval_ms
+            .clamp(MIN_REFRESH_INTERVAL_MS, MAX_REFRESH_INTERVAL_MS);
+        std::sync::atomic::AtomicU64::new(ms)
+    });
+
+pub fn set_refresh_interval(ms: u64) -> AppResult<u64> {
+    let ms = ms.clamp(MIN_REFRESH_INTERVAL_MS, MAX_REFRESH_INTERVAL_MS);
+    crate::config::update(|c| {
+        c.stats_refresh_interval_ms = ms;
+        Ok(())
+    })?;
+    REFRESH_INTERVAL_MS.store(ms, std::sync::atomic::Ordering::Relaxed);
+    Ok(ms)
+}
+
 static SYS: std::sync::LazyLock<Mutex<(System, Instant)>> = std::sync::LazyLock::new(|| {
     let mut sys = System::new();
     sys.refresh_cpu_all();
-    std::thread::sleep(std::time::Duration::from_millis(200));
+    // CPU usage needs two samples at least this far apart to mean anything
+    std::thread::sleep(sysinfo::MINIMUM_CPU_UPDATE_INTERVAL);
     sys.refresh_cpu_all();
     sys.refresh_memory_specifics(MemoryRefreshKind::everything());
     Mutex::new((sys, Instant::now()))
 });
 
+// libnotify, part of libSystem; the thermal pressure level is published as
+// notification state, which is what NSProcessInfo.thermalState reads
+extern "C" {
+    fn notify_register_check(name: *const libc::c_char, out_token: *mut libc::c_int) -> u32;
+    fn notify_get_state(token: libc::c_int, state: *mut u64) -> u32;
+}
+
+static THERMAL_TOKEN: std::sync::LazyLock<Option<libc::c_int>> = std::sync::LazyLock::new(|| {
+    let mut token: libc::c_int = 0;
+    let status = unsafe {
+        notify_register_check(c"com.apple.system.thermalpressurelevel".as_ptr(), &mut token)
+    };
+    if status == 0 { Some(token) } else { None }
+});
+
+pub fn thermal_state() -> &'static str {
+    let Some(token) = *THERMAL_TOKEN else { return "unknown" };
+    let mut level: u64 = 0;
+    if unsafe { notify_get_state(token, &mut level) } != 0 {
+        return "unknown";
+    }
+    // OSThermalPressureLevel: nominal, moderate, heavy, trapping, sleeping
+    match level {
+        0 => "nominal",
+        1 => "fair",
+        2 => "serious",
+        _ => "critical",
+    }
+}
+
+extern "C" {
+    fn mach_host_self() -> libc::mach_port_t;
+    fn processor_set_default(host: libc::mach_port_t, default_set: *mut libc::mach_port_t) -> libc::kern_return_t;
+    fn processor_set_statistics(
+        pset: libc::mach_port_t,
+        flavor: libc::c_int,
+        info: *mut libc::integer_t,
+        count: *mut libc::mach_msg_type_number_t,
+    ) -> libc::kern_return_t;
+    static mach_task_self_: libc::mach_port_t;
+    fn host_processor_info(
+        host: libc::mach_port_t,
+        flavor: libc::c_int,
+        out_processor_count: *mut libc::natural_t,
+        out_processor_info: *mut *mut libc::integer_t,
+        out_processor_info_count: *mut libc::mach_msg_type_number_t,
+    ) -> libc::kern_return_t;
+    fn vm_deallocate(target: libc::mach_port_t, address: libc::vm_address_t, size: libc::vm_size_t) -> libc::kern_return_t;
+}
+
+// <mach/processor_info.h>
+const PROCESSOR_CPU_LOAD_INFO: libc::c_int = 2;
+const CPU_STATE_MAX: usize = 4; // user, system, idle, nice
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    fn CGMainDisplayID() -> u32;
+    fn CGDisplayIsAsleep(display: u32) -> u32;
+}
+
+// Host and default processor set ports; looked up once so we don't leak a
+// port reference on every call
+static HOST: std::sync::LazyLock<libc::mach_port_t> =
+    std::sync::LazyLock::new(|| unsafe { mach_host_self() });
+
+static PSET: std::sync::LazyLock<Option<libc::mach_port_t>> = std::sync::LazyLock::new(|| {
+    let mut pset: libc::mach_port_t = 0;
+    let kr = unsafe { processor_set_default(*HOST, &mut pset) };
+    if kr == 0 { Some(pset) } else { None }
+});
+
+fn vm_stats() -> Option<libc::vm_statistics64> {
+    let mut stats: libc::vm_statistics64 = unsafe { std::mem::zeroed() };
+    let mut count = libc::HOST_VM_INFO64_COUNT;
+    let kr = unsafe {
+        libc::host_statistics64(
+            *HOST,
+            libc::HOST_VM_INFO64,
+            &mut stats as *mut _ as libc::host_info64_t,
+            &mut count,
+        )
+    };
+    if kr == 0 { Some(stats) } else { None }
+}
+
+// Last raw per-core counters, and the running totals built from their deltas
+static TICKS: std::sync::LazyLock<Mutex<(Vec<[u32; CPU_STATE_MAX]>, [u64; CPU_STATE_MAX])>> =
+    std::sync::LazyLock::new(|| Mutex::new((Vec::new(), [0; CPU_STATE_MAX])));
+
+fn cpu_ticks() -> Option<CpuTicks> {
+    let mut cpu_count: libc::natural_t = 0;
+    let mut info: *mut libc::integer_t = std::ptr::null_mut();
+    let mut info_count: libc::mach_msg_type_number_t = 0;
+    let kr = unsafe {
+        host_processor_info(*HOST, PROCESSOR_CPU_LOAD_INFO, &mut cpu_count, &mut info, &mut info_count)
+    };
+    if kr != 0 || info.is_null() { return None; }
+
+    let raw = unsafe { std::slice::from_raw_parts(info, info_count as usize) };
+    let cores: Vec<[u32; CPU_STATE_MAX]> = raw.chunks_exact(CPU_STATE_MAX)
+        .take(cpu_count as usize)
+        .map(|core| std::array::from_fn(|i| core[i] as u32))
+        .collect();
+    // The kernel allocated the array in our address space
+    unsafe {
+        vm_deallocate(
+            mach_task_self_,
+            info as libc::vm_address_t,
+            info_count as usize * std::mem::size_of::<libc::integer_t>(),
+        );
+    }
+
+    let mut state = TICKS.lock().unwrap_or_else(|e| e.into_inner());
+    let (last, totals) = &mut *state;
+    for (i, core) in cores.iter().enumerate() {
+        // The first sample of a core counts everything since boot
+        let prev = last.get(i).copied().unwrap_or([0; CPU_STATE_MAX]);
+        for ((total, now), before) in totals.iter_mut().zip(core).zip(prev) {
+            *total += now.wrapping_sub(before) as u64;
+        }
+    }
+    *last = cores;
+    Some(CpuTicks { user: totals[0], system: totals[1], idle: totals[2], nice: totals[3] })
+}
+
+// Bytes the system can hand out without paging anything to swap
+fn available_memory(vm: &libc::vm_statistics64) -> u64 {
+    pages_to_bytes(vm.free_count as u64 + vm.purgeable_count as u64 + vm.external_page_count as u64)
+}
+
+fn pages_to_bytes(pages: u64) -> u64 {
+    let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(0) as u64;
+    pages * page_size
+}
+
+const SWAP_DIR: &str = "/private/var/vm";
+
+// dynamic_pager names them swapfile0, swapfile1, ... and adds one each time
+// the existing ones fill up, so the count tracks sustained pressure
+fn swap_files() -> Vec<String> {
+    let mut files: Vec<(u32, String)> = std::fs::read_dir(SWAP_DIR)
+        .map(|entries| entries.flatten().filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+            let index = name.strip_prefix("swapfile")?.parse().ok()?;
+            Some((index, entry.path().to_string_lossy().to_string()))
+        }).collect())
+        .unwrap_or_default();
+    files.sort();
+    files.into_iter().map(|(_, path)| path).collect()
+}
+
+// (swapins, swapouts, taken at) from the previous stats call
+static LAST_SWAP: std::sync::LazyLock<Mutex<Option<(u64, u64, Instant)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+fn swap_rates(vm: Option<&libc::vm_statistics64>) -> (f64, f64) {
+    let Some(vm) = vm else { return (0.0, 0.0) };
+    let mut last = LAST_SWAP.lock().unwrap_or_else(|e| e.into_inner());
+    let rates = match *last {
+        Some((ins, outs, at)) => {
+            let secs = at.elapsed().as_secs_f64();
+            if secs > 0.0 {
+                (
+                    vm.swapins.saturating_sub(ins) as f64 / secs,
+                    vm.swapouts.saturating_sub(outs) as f64 / secs,
+                )
+            } else {
+                (0.0, 0.0)
+            }
+        }
+        None => (0.0, 0.0),
+    };
+    *last = Some((vm.swapins, vm.swapouts, Instant::now()));
+    rates
+}
+
+// System-wide (tasks, threads) from the kernel's processor set load info —
+// the same numbers `top` shows, without enumerating processes
+fn task_and_thread_counts() -> Option<(usize, usize)> {
+    let pset = (*PSET)?;
+    let mut info: libc::processor_set_load_info = unsafe { std::mem::zeroed() };
+    let mut count = (std::mem::size_of::<libc::processor_set_load_info>()
+        / std::mem::size_of::<libc::integer_t>()) as libc::mach_msg_type_number_t;
+    let kr = unsafe {
+        processor_set_statistics(
+            pset,
+            libc::PROCESSOR_SET_LOAD_INFO,
+            &mut info as *mut _ as *mut libc::integer_t,
+            &mut count,
+        )
+    };
+    if kr != 0 { return None; }
+    Some((info.task_count.max(0) as usize, info.thread_count.max(0) as usize))
+}
+
+// Trend history: one sample per TREND_INTERVAL, newest last
+const TREND_INTERVAL_MS: i64 = 5_000;
+const TREND_CAPACITY: usize = 720; // 1 hour
+const TREND_RECENT: usize = 60;    // newest samples returned at full resolution
+const TREND_BUCKET: usize = 12;    // older samples are averaged per minute
+
+static TRENDS: std::sync::LazyLock<Mutex<std::collections::VecDeque<TrendSample>>> =
+    std::sync::LazyLock::new(|| Mutex::new(std::collections::VecDeque::new()));
+
+fn record_trend(process_count: usize, thread_count: usize, load_avg_one: f64) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut trends = TRENDS.lock().unwrap_or_else(|e| e.into_inner());
+    if trends.back().map(|s| now - s.timestamp < TREND_INTERVAL_MS).unwrap_or(false) {
+        return;
+    }
+    trends.push_back(TrendSample { timestamp: now, process_count, thread_count, load_avg_one });
+    if trends.len() > TREND_CAPACITY {
+        trends.pop_front();
+    }
+}
+
+pub fn get_system_trends() -> SystemTrends {
+    let trends = TRENDS.lock().unwrap_or_else(|e| e.into_inner());
+    let split = trends.len().saturating_sub(TREND_RECENT);
+    let older: Vec<&TrendSample> = trends.range(..split).collect();
+
+    let mut samples: Vec<TrendSample> = older
+        .chunks(TREND_BUCKET)
+        .map(|chunk| {
+            let n = chunk.len();
+            TrendSample {
+                timestamp: chunk[n - 1].timestamp,
+                process_count: chunk.iter().map(|s| s.process_count).sum::<usize>() / n,
+                thread_count: chunk.iter().map(|s| s.thread_count).sum::<usize>() / n,
+                load_avg_one: chunk.iter().map(|s| s.load_avg_one).sum::<f64>() / n as f64,
+            }
+        })
+        .collect();
+    samples.extend(trends.range(split..).cloned());
+
+    SystemTrends { uptime_secs: System::uptime(), samples }
+}
+
+// Per-core usage history for the heat map: finer than the trend samples but
+// kept for less time, since each sample holds one value per core
+const CORE_HISTORY_INTERVAL_MS: i64 = 1_000;
+const CORE_HISTORY_CAPACITY: usize = 600; // 10 minutes
+
+static CORE_HISTORY: std::sync::LazyLock<Mutex<std::collections::VecDeque<(i64, Vec<f32>)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(std::collections::VecDeque::new()));
+
+fn record_core_usage(sys: &System) {
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut history = CORE_HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+    if history.back().map(|(t, _)| now - t < CORE_HISTORY_INTERVAL_MS).unwrap_or(false) {
+        return;
+    }
+    let usage = sys.cpus().iter().map(|c| (c.cpu_usage() * 10.0).round() / 10.0).collect();
+    history.push_back((now, usage));
+    if history.len() > CORE_HISTORY_CAPACITY {
+        history.pop_front();
+    }
+}
+
+// Oldest first, one row per sample and one column per core (same order as
+// get_core_details)
+pub fn get_per_core_history(seconds: u64) -> Vec<Vec<f32>> {
+    let since = chrono::Utc::now().timestamp_millis() - (seconds as i64).saturating_mul(1000);
+    let history = CORE_HISTORY.lock().unwrap_or_else(|e| e.into_inner());
+    history.iter()
+        .filter(|(t, _)| *t >= since)
+        .map(|(_, usage)| usage.clone())
+        .collect()
+}
+
 fn format_uptime(secs: u64) -> String {
     let days = secs / 86400;
     let hours = (secs % 86400) / 3600;
@@ -82,16 +477,319 @@ fn format_uptime(secs: u64) -> String {
     }
 }
 
-pub fn get_system_stats() -> SystemStats {
-    let mut guard = SYS.lock().unwrap();
-    let (sys, last) = &mut *guard;
+// BSD device backing a mount point, e.g. "/dev/disk3s1s1"
+fn mount_device(st: &libc::statfs) -> String {
+    let from = unsafe { std::ffi::CStr::from_ptr(st.f_mntfromname.as_ptr()) };
+    from.to_string_lossy().into_owned()
+}
+
+fn statfs_for(mount: &std::path::Path) -> Option<libc::statfs> {
+    let c_path = std::ffi::CString::new(mount.to_string_lossy().as_bytes()).ok()?;
+    let mut st: libc::statfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statfs(c_path.as_ptr(), &mut st) } != 0 {
+        return None;
+    }
+    Some(st)
+}
+
+// Names for the statfs flags worth showing, in `mount` output order
+const MOUNT_FLAGS: &[(libc::c_int, &str)] = &[
+    (libc::MNT_RDONLY, "read-only"),
+    (libc::MNT_SYNCHRONOUS, "synchronous"),
+    (libc::MNT_NOEXEC, "noexec"),
+    (libc::MNT_NOSUID, "nosuid"),
+    (libc::MNT_NODEV, "nodev"),
+    (libc::MNT_ASYNC, "asynchronous"),
+    (libc::MNT_LOCAL, "local"),
+    (libc::MNT_QUOTA, "quotas"),
+    (libc::MNT_ROOTFS, "root file system"),
+    (libc::MNT_JOURNALED, "journaled"),
+    (libc::MNT_DONTBROWSE, "nobrowse"),
+    (libc::MNT_IGNORE_OWNERSHIP, "noowners"),
+    (libc::MNT_AUTOMOUNTED, "automounted"),
+    (libc::MNT_QUARANTINE, "quarantine"),
+    (libc::MNT_NOATIME, "noatime"),
+    (libc::MNT_SNAPSHOT, "sealed"),
+    (libc::MNT_CPROTECT, "protect"),
+];
+
+fn mount_options(st: &libc::statfs) -> Vec<String> {
+    MOUNT_FLAGS.iter()
+        .filter(|(flag, _)| st.f_flags & (*flag as u32) != 0)
+        .map(|(_, name)| name.to_string())
+        .collect()
+}
+
+// "/dev/disk3s1s1" -> "disk3": APFS volumes in one container share its space
+fn apfs_container(device: &str) -> String {
+    let name = device.trim_start_matches("/dev/");
+    let digits = name["disk".len()..].chars().take_while(|c| c.is_ascii_digit()).count();
+    name[.."disk".len() + digits].to_string()
+}
+
+// APFS role per volume device ("disk3s5" -> "Data"). Roles are fixed, but
+// volumes can appear later, so a miss triggers one rescan; devices the
+// listing doesn't know (e.g. Time Machine snapshot mounts) are then cached
+// with an empty role.
+static APFS_ROLES: std::sync::LazyLock<Mutex<std::collections::HashMap<String, String>>> =
+    std::sync::LazyLock::new(|| Mutex::new(std::collections::HashMap::new()));
+
+fn scan_apfs_roles() -> std::collections::HashMap<String, String> {
+    let mut roles = std::collections::HashMap::new();
+    let Ok(output) = std::process::Command::new("diskutil").args(["apfs", "list", "-plist"]).output() else {
+        return roles;
+    };
+    let Ok(val) = plist::Value::from_reader(std::io::Cursor::new(output.stdout)) else { return roles };
+    let containers = val.as_dictionary()
+        .and_then(|d| d.get("Containers"))
+        .and_then(|v| v.as_array());
+
+    for container in containers.into_iter().flatten() {
+        let volumes = container.as_dictionary()
+            .and_then(|d| d.get("Volumes"))
+            .and_then(|v| v.as_array());
+        for volume in volumes.into_iter().flatten().filter_map(|v| v.as_dictionary()) {
+            let Some(device) = volume.get("DeviceIdentifier").and_then(|v| v.as_string()) else { continue };
+            let role = volume.get("Roles")
+                .and_then(|v| v.as_array())
+                .and_then(|r| r.first())
+                .and_then(|r| r.as_string())
+                .unwrap_or_default(); // role-less volumes are remembered too, so they don't force rescans
+            roles.insert(device.to_string(), role.to_string());
+        }
+    }
+    roles
+}
+
+fn apfs_role(st: &libc::statfs) -> Option<String> {
+    let fs_type = unsafe { std::ffi::CStr::from_ptr(st.f_fstypename.as_ptr()) };
+    if fs_type.to_bytes() != b"apfs" { return None; }
+
+    let device = mount_device(st);
+    let name = device.trim_start_matches("/dev/");
+    // The sealed system volume mounts as a snapshot ("disk3s1s1") of "disk3s1"
+    let parent = name.rfind('s')
+        .filter(|&i| i > "disk".len() && name[i + 1..].chars().all(|c| c.is_ascii_digit()))
+        .map(|i| &name[..i]);
+    let lookup = |roles: &std::collections::HashMap<String, String>| {
+        roles.get(name).or_else(|| parent.and_then(|p| roles.get(p))).cloned()
+    };
+
+    if let Some(role) = lookup(&APFS_ROLES.lock().unwrap_or_else(|e| e.into_inner())) {
+        return Some(role).filter(|r| !r.is_empty());
+    }
+    // diskutil is slow; other stats readers shouldn't wait on the lock for it
+    let scanned = scan_apfs_roles();
+    let mut roles = APFS_ROLES.lock().unwrap_or_else(|e| e.into_inner());
+    roles.extend(scanned);
+    let role = lookup(&roles);
+    if role.is_none() {
+        roles.insert(name.to_string(), String::new());
+    }
+    role.filter(|r| !r.is_empty())
+}
+
+// `diskutil info` takes long enough that it can't run on every stats tick,
+// and purgeable space only shifts slowly anyway
+const PURGEABLE_TTL: std::time::Duration = std::time::Duration::from_secs(60);
+
+static ROOT_PURGEABLE: std::sync::LazyLock<Mutex<Option<(Instant, Option<u64>)>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+fn read_purgeable(mount: &str) -> Option<u64> {
+    let output = std::process::Command::new("diskutil").args(["info", "-plist", mount]).output().ok()?;
+    let val = plist::Value::from_reader(std::io::Cursor::new(output.stdout)).ok()?;
+    val.as_dictionary()?.get("APFSPurgeableSpace")?.as_unsigned_integer()
+}
+
+fn root_purgeable() -> Option<u64> {
+    let mut cached = ROOT_PURGEABLE.lock().unwrap_or_else(|e| e.into_inner());
+    if let Some((at, value)) = *cached {
+        if at.elapsed() < PURGEABLE_TTL {
+            return value;
+        }
+    }
+    let value = read_purgeable("/");
+    *cached = Some((Instant::now(), value));
+    value
+}
+
+fn aggregate_disk_stats(disks: &Disks) -> DiskStats {
+    let mut seen = std::collections::HashSet::new();
+    let (mut total, mut free) = (0u64, 0u64);
+
+    for disk in disks.iter() {
+        // Only local, writable block devices; skips network shares, synthetic
+        // mounts and the sealed system volume
+        let Some(st) = statfs_for(disk.mount_point()) else { continue };
+        let device = mount_device(&st);
+        if !device.starts_with("/dev/disk") { continue; }
+        if st.f_flags & libc::MNT_RDONLY as u32 != 0 { continue; }
 
-    // Only refresh CPU if >500ms since last refresh
-    if last.elapsed().as_millis() > 500 {
+        // Every volume in an APFS container reports the container's size and
+        // shared free space, so count each container once
+        let key = if disk.file_system() == "apfs" { apfs_container(&device) } else { device };
+        if !seen.insert(key) { continue; }
+
+        total += disk.total_space();
+        free += disk.available_space();
+    }
+
+    let used = total.saturating_sub(free);
+    DiskStats {
+        total,
+        used,
+        free,
+        used_percent: if total > 0 { (used as f64 / total as f64) * 100.0 } else { 0.0 },
+        mount_point: "*".into(),
+        read_only: false,
+        mount_options: Vec::new(),
+        role: None,
+        purgeable: None,
+    }
+}
+
+fn refresh_if_stale(sys: &mut System, last: &mut Instant) {
+    let interval = REFRESH_INTERVAL_MS.load(std::sync::atomic::Ordering::Relaxed);
+    if last.elapsed().as_millis() as u64 > interval {
+        let started = Instant::now();
         sys.refresh_cpu_all();
         sys.refresh_memory_specifics(MemoryRefreshKind::everything());
+        timings::record(Stage::CpuRefresh, started);
         *last = Instant::now();
     }
+}
+
+fn sysctl_u32(name: &std::ffi::CStr) -> Option<u32> {
+    let mut value: u32 = 0;
+    let mut size = std::mem::size_of::<u32>();
+    let ret = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            &mut value as *mut _ as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ret == 0 { Some(value) } else { None }
+}
+
+fn sysctl_string(name: &std::ffi::CStr) -> Option<String> {
+    let mut size: libc::size_t = 0;
+    let ret = unsafe {
+        libc::sysctlbyname(name.as_ptr(), std::ptr::null_mut(), &mut size, std::ptr::null_mut(), 0)
+    };
+    if ret != 0 || size == 0 { return None; }
+
+    let mut buf = vec![0u8; size];
+    let ret = unsafe {
+        libc::sysctlbyname(
+            name.as_ptr(),
+            buf.as_mut_ptr() as *mut libc::c_void,
+            &mut size,
+            std::ptr::null_mut(),
+            0,
+        )
+    };
+    if ret != 0 { return None; }
+    let s = std::ffi::CStr::from_bytes_until_nul(&buf).ok()?;
+    Some(s.to_string_lossy().into_owned())
+}
+
+// Build number, e.g. "24B83"; never changes while we're running
+static OS_BUILD: std::sync::LazyLock<Option<String>> =
+    std::sync::LazyLock::new(|| sysctl_string(c"kern.osversion"));
+
+const OS_NAME: &str = "macOS";
+
+static MODEL_IDENTIFIER: std::sync::LazyLock<Option<String>> =
+    std::sync::LazyLock::new(|| sysctl_string(c"hw.model"));
+
+static MODEL_NAME: std::sync::LazyLock<Option<String>> =
+    std::sync::LazyLock::new(|| cached_marketing_name().or_else(profiler_model_name));
+
+// About This Mac caches the full marketing name (with size and year) here
+fn cached_marketing_name() -> Option<String> {
+    let path = dirs::home_dir()?.join("Library/Preferences/com.apple.SystemProfiler.plist");
+    let val = plist::Value::from_file(path).ok()?;
+    val.as_dictionary()?
+        .get("CPU Names")?
+        .as_dictionary()?
+        .values()
+        .find_map(|v| v.as_string().map(|s| s.to_string()))
+}
+
+// Fallback without the year, e.g. "Mac mini"
+fn profiler_model_name() -> Option<String> {
+    let output = std::process::Command::new("system_profiler")
+        .args(["SPHardwareDataType", "-json"])
+        .output()
+        .ok()?;
+    let val: serde_json::Value = serde_json::from_slice(&output.stdout).ok()?;
+    val["SPHardwareDataType"][0]["machine_name"].as_str().map(|s| s.to_string())
+}
+
+// The console session's lock state is published on the IORegistry root, the
+// same flag CGSessionCopyCurrentDictionary reports
+fn screen_locked() -> bool {
+    let Ok(output) = std::process::Command::new("ioreg")
+        .args(["-a", "-n", "Root", "-d", "1"])
+        .output()
+    else {
+        return false;
+    };
+    let Ok(val) = plist::Value::from_reader(std::io::Cursor::new(output.stdout)) else { return false };
+    val.as_dictionary()
+        .and_then(|d| d.get("IOConsoleUsers"))
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|u| u.as_dictionary())
+        .any(|u| u.get("CGSSessionScreenIsLocked").and_then(|v| v.as_boolean()).unwrap_or(false))
+}
+
+pub fn get_display_state() -> DisplayState {
+    DisplayState {
+        asleep: unsafe { CGDisplayIsAsleep(CGMainDisplayID()) } != 0,
+        locked: screen_locked(),
+    }
+}
+
+pub fn get_core_details() -> Vec<CoreDetail> {
+    let mut guard = SYS.lock().unwrap_or_else(|e| e.into_inner());
+    let (sys, last) = &mut *guard;
+    refresh_if_stale(sys, last);
+
+    // On Apple Silicon perflevel0 is the P cluster and perflevel1 the E
+    // cluster; the kernel numbers E cores first. Intel has neither sysctl.
+    let e_cores = sysctl_u32(c"hw.perflevel1.logicalcpu").map(|n| n as usize);
+    let has_clusters = sysctl_u32(c"hw.perflevel0.logicalcpu").is_some();
+
+    sys.cpus().iter().enumerate().map(|(index, cpu)| {
+        let cluster = match (has_clusters, e_cores) {
+            (true, Some(e)) if index < e => Some("efficiency".to_string()),
+            (true, _) => Some("performance".to_string()),
+            _ => None,
+        };
+        CoreDetail {
+            index,
+            cluster,
+            usage: (cpu.cpu_usage() * 10.0).round() / 10.0,
+            frequency_mhz: Some(cpu.frequency()).filter(|f| *f > 0),
+        }
+    }).collect()
+}
+
+// The most recent get_system_stats result, for readers that mustn't advance
+// its sampling state (swap-rate baseline, trend and core history)
+static LAST_STATS: std::sync::LazyLock<Mutex<Option<SystemStats>>> =
+    std::sync::LazyLock::new(|| Mutex::new(None));
+
+pub fn get_system_stats() -> SystemStats {
+    let mut guard = SYS.lock().unwrap_or_else(|e| e.into_inner());
+    let (sys, last) = &mut *guard;
+    refresh_if_stale(sys, last);
 
     let cpus = sys.cpus();
     let cpu_count = cpus.len();
@@ -119,8 +817,20 @@ pub fn get_system_stats() -> SystemStats {
         .map(|d| (d.total_space(), d.available_space()))
         .unwrap_or((0, 0));
     let disk_used = disk_total.saturating_sub(disk_free);
+    let aggregate_disk = aggregate_disk_stats(&disks);
+    let root_stat = statfs_for(std::path::Path::new("/"));
+    let vm = vm_stats();
+    let (swap_ins, swap_outs) = swap_rates(vm.as_ref());
+    let swap_files = swap_files();
+    let (task_count, thread_count) = task_and_thread_counts()
+        .unwrap_or((sys.processes().len(), 0));
+    // The processor set's task count includes tasks the process list can't
+    // see, so prefer the list's own length once there is one
+    let process_count = crate::processes::process_count().unwrap_or(task_count);
+    record_trend(process_count, thread_count, load_avg.one);
+    record_core_usage(sys);
 
-    SystemStats {
+    let stats = SystemStats {
         cpu: CpuStats {
             user,
             sys: sys_usage,
@@ -128,32 +838,114 @@ pub fn get_system_stats() -> SystemStats {
             model: cpu_model,
             cores: cpu_count,
             load_avg: [load_avg.one, load_avg.five, load_avg.fifteen],
+            load_avg_normalized: [load_avg.one, load_avg.five, load_avg.fifteen]
+                .map(|l| if cpu_count > 0 { l / cpu_count as f64 } else { 0.0 }),
+            ticks: cpu_ticks(),
         },
         memory: MemoryStats {
             total: total_mem,
             used: used_mem,
             free: free_mem,
-            wired: 0,       // sysinfo doesn't split wired/compressed
-            compressed: 0,
+            available: vm.as_ref().map(available_memory).unwrap_or(free_mem),
+            // Page counts from the same vm_statistics64 read; zero if it failed
+            wired: vm.as_ref().map(|v| pages_to_bytes(v.wire_count as u64)).unwrap_or(0),
+            compressed: vm.as_ref().map(|v| pages_to_bytes(v.compressor_page_count as u64)).unwrap_or(0),
+            active: vm.as_ref().map(|v| pages_to_bytes(v.active_count as u64)).unwrap_or(0),
+            inactive: vm.as_ref().map(|v| pages_to_bytes(v.inactive_count as u64)).unwrap_or(0),
+            purgeable: vm.as_ref().map(|v| pages_to_bytes(v.purgeable_count as u64)).unwrap_or(0),
             used_percent: used_pct,
         },
+        swap: SwapStats {
+            total: sys.total_swap(),
+            used: sys.used_swap(),
+            swap_ins_per_sec: swap_ins,
+            swap_outs_per_sec: swap_outs,
+            swap_file_count: swap_files.len(),
+            swap_files,
+        },
         disk: DiskStats {
             total: disk_total,
             used: disk_used,
             free: disk_free,
             used_percent: if disk_total > 0 { (disk_used as f64 / disk_total as f64) * 100.0 } else { 0.0 },
             mount_point: "/".into(),
+            read_only: root_stat.as_ref().map(|st| st.f_flags & libc::MNT_RDONLY as u32 != 0).unwrap_or(false),
+            mount_options: root_stat.as_ref().map(mount_options).unwrap_or_default(),
+            role: root_stat.as_ref().and_then(apfs_role),
+            purgeable: root_purgeable(),
         },
+        aggregate_disk,
         uptime: format_uptime(System::uptime()),
         hostname: System::host_name().unwrap_or_else(|| "localhost".into()),
         os_version: System::os_version().unwrap_or_else(|| "unknown".into()),
-        process_count: sys.processes().len(),
-        thread_count: 0,
+        os_build: OS_BUILD.clone(),
+        os_name: OS_NAME.into(),
+        process_count,
+        thread_count,
+        zombie_count: crate::processes::zombie_count(),
+        thermal_state: thermal_state().into(),
+    };
+    *LAST_STATS.lock().unwrap_or_else(|e| e.into_inner()) = Some(stats.clone());
+    stats
+}
+
+// Health score weights; they sum to 100, so each is the most that factor can
+// take off the score. Every factor maps its signal to 0..1 between a "fine"
+// and a "bad" level, linearly.
+const HEALTH_WEIGHT_CPU: f64 = 20.0;      // busy share, 50% .. 100%
+const HEALTH_WEIGHT_MEMORY: f64 = 25.0;   // unavailable share, 70% .. 95%
+const HEALTH_WEIGHT_SWAP: f64 = 15.0;     // swap-outs, 0 .. 500 pages/s
+const HEALTH_WEIGHT_DISK: f64 = 25.0;     // used share of all volumes, 80% .. 98%
+const HEALTH_WEIGHT_THERMAL: f64 = 15.0;  // nominal .. critical
+
+fn ramp(value: f64, fine: f64, bad: f64) -> f64 {
+    ((value - fine) / (bad - fine)).clamp(0.0, 1.0)
+}
+
+// Best-effort split of wired memory. Walks every process, so it's kept out of
+// the frequently polled SystemStats.
+pub fn get_wired_breakdown() -> WiredBreakdown {
+    let total = vm_stats().map(|v| pages_to_bytes(v.wire_count as u64)).unwrap_or(0);
+    let processes = crate::processes::wired_by_processes().min(total);
+    WiredBreakdown { total, kernel: total - processes, processes }
+}
+
+// Scored from the UI's last stats poll; only samples itself before the first one
+pub fn get_health_score() -> HealthScore {
+    let last = LAST_STATS.lock().unwrap_or_else(|e| e.into_inner()).clone();
+    let stats = last.unwrap_or_else(get_system_stats);
+    let mem = &stats.memory;
+    let unavailable = if mem.total > 0 { 100.0 - mem.available as f64 / mem.total as f64 * 100.0 } else { 0.0 };
+    let thermal = match stats.thermal_state.as_str() {
+        "fair" => 0.33,
+        "serious" => 0.66,
+        "critical" => 1.0,
+        _ => 0.0,
+    };
+
+    let mut factors = vec![
+        ("cpu", HEALTH_WEIGHT_CPU * ramp(100.0 - stats.cpu.idle as f64, 50.0, 100.0)),
+        ("memory", HEALTH_WEIGHT_MEMORY * ramp(unavailable, 70.0, 95.0)),
+        ("swap", HEALTH_WEIGHT_SWAP * ramp(stats.swap.swap_outs_per_sec, 0.0, 500.0)),
+        ("disk", HEALTH_WEIGHT_DISK * ramp(stats.aggregate_disk.used_percent, 80.0, 98.0)),
+        ("thermal", HEALTH_WEIGHT_THERMAL * thermal),
+    ];
+    factors.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    let penalty: f64 = factors.iter().map(|(_, p)| p).sum();
+    HealthScore {
+        score: (100.0 - penalty).round().clamp(0.0, 100.0) as u8,
+        top_factor: factors.first().filter(|(_, p)| *p >= 1.0).map(|(name, _)| name.to_string()),
+        factors: factors.into_iter()
+            .map(|(name, p)| HealthFactor { name: name.into(), penalty: (p * 10.0).round() / 10.0 })
+            .collect(),
     }
 }
 
 pub fn get_hardware_info() -> HardwareInfo {
-    let guard = SYS.lock().unwrap();
+    // First call may shell out to system_profiler; don't hold SYS while it does
+    let model_name = MODEL_NAME.clone();
+    let guard = SYS.lock().unwrap_or_else(|e| e.into_inner());
     let (sys, _) = &*guard;
 
     let cpus = sys.cpus();
@@ -162,10 +954,14 @@ pub fn get_hardware_info() -> HardwareInfo {
 
     HardwareInfo {
         model: model.into(),
+        model_identifier: MODEL_IDENTIFIER.clone(),
+        model_name,
         cpu: cpu_model.clone(),
         cores: cpus.len(),
         memory: sys.total_memory(),
         os_version: System::os_version().unwrap_or_else(|| "unknown".into()),
+        os_build: OS_BUILD.clone(),
+        os_name: OS_NAME.into(),
         hostname: System::host_name().unwrap_or_else(|| "localhost".into()),
         serial_number: None,
     }
@@ -0,0 +1,38 @@
+use serde::Serialize;
+use std::sync::Mutex;
+use std::time::Instant;
+
+// Most recent duration of each gather, in milliseconds. None until that path has run once.
+#[derive(Serialize, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GatherTimings {
+    pub cpu_refresh_ms: Option<f64>,
+    pub process_enumeration_ms: Option<f64>,
+    pub service_discovery_ms: Option<f64>,
+    pub log_query_ms: Option<f64>,
+}
+
+pub enum Stage {
+    CpuRefresh,
+    ProcessEnumeration,
+    ServiceDiscovery,
+    LogQuery,
+}
+
+static TIMINGS: std::sync::LazyLock<Mutex<GatherTimings>> =
+    std::sync::LazyLock::new(|| Mutex::new(GatherTimings::default()));
+
+pub fn record(stage: Stage, started: Instant) {
+    let ms = Some(started.elapsed().as_secs_f64() * 1000.0);
+    let mut t = TIMINGS.lock().unwrap_or_else(|e| e.into_inner());
+    match stage {
+        Stage::CpuRefresh => t.cpu_refresh_ms = ms,
+        Stage::ProcessEnumeration => t.process_enumeration_ms = ms,
+        Stage::ServiceDiscovery => t.service_discovery_ms = ms,
+        Stage::LogQuery => t.log_query_ms = ms,
+    }
+}
+
+pub fn get_gather_timings() -> GatherTimings {
+    TIMINGS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
@@ -1,22 +1,47 @@
 use tauri::{
     tray::{TrayIconBuilder, MouseButton, MouseButtonState, TrayIconEvent},
-    menu::{MenuBuilder, MenuItemBuilder},
-    Manager,
+    menu::{Menu, MenuBuilder, MenuItemBuilder, PredefinedMenuItem},
+    AppHandle, Manager, Wry,
 };
 
+use crate::power;
+
+const BATTERY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+// Kept in app state so items can be added after startup
+struct TrayMenu(Menu<Wry>);
+
+// The update item goes in once, however many checks find the update
+static UPDATE_SHOWN: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
 pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
     let quit = MenuItemBuilder::with_id("quit", "Quit Mac Dash").build(app)?;
     let show = MenuItemBuilder::with_id("show", "Show Dashboard").build(app)?;
 
-    let menu = MenuBuilder::new(app)
+    // Only laptops get the battery line; desktop Macs have no AppleSmartBattery
+    let battery = power::get_battery_info();
+    let battery_item = match &battery {
+        Some(info) => Some(
+            MenuItemBuilder::with_id("battery", power::battery_summary(info))
+                .enabled(false)
+                .build(app)?,
+        ),
+        None => None,
+    };
+
+    let mut menu = MenuBuilder::new(app);
+    if let Some(item) = &battery_item {
+        menu = menu.item(item).separator();
+    }
+    let menu = menu
         .item(&show)
         .separator()
         .item(&quit)
         .build()?;
 
-    let _tray = TrayIconBuilder::new()
+    let tray = TrayIconBuilder::new()
         .menu(&menu)
-        .tooltip("Mac Dash")
+        .tooltip(tooltip(battery.as_ref()))
         .on_menu_event(move |app, event| {
             match event.id().as_ref() {
                 "quit" => {
@@ -28,6 +53,12 @@ pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
                         let _ = window.set_focus();
                     }
                 }
+                "install_update" => {
+                    let app = app.clone();
+                    tauri::async_runtime::spawn(async move {
+                        let _ = crate::install_update(app).await;
+                    });
+                }
                 _ => {}
             }
         })
@@ -41,6 +72,46 @@ pub fn setup_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>> {
             }
         })
         .build(app)?;
+    app.manage(TrayMenu(menu));
+
+    if let Some(item) = battery_item {
+        std::thread::spawn(move || loop {
+            std::thread::sleep(BATTERY_POLL_INTERVAL);
+            let battery = power::get_battery_info();
+            if let Some(info) = &battery {
+                let _ = item.set_text(power::battery_summary(info));
+            }
+            let _ = tray.set_tooltip(Some(tooltip(battery.as_ref())));
+        });
+    }
 
     Ok(())
 }
+
+// Puts an "Update available" item at the top of the tray menu and posts a
+// notification, the first time a check finds a new version
+pub fn show_update_available(app: &AppHandle, version: &str) -> tauri::Result<()> {
+    use tauri_plugin_notification::NotificationExt;
+
+    if UPDATE_SHOWN.swap(true, std::sync::atomic::Ordering::Relaxed) {
+        return Ok(());
+    }
+    let menu = &app.state::<TrayMenu>().0;
+    let item = MenuItemBuilder::with_id("install_update", format!("Update available ({})", version)).build(app)?;
+    menu.insert(&item, 0)?;
+    menu.insert(&PredefinedMenuItem::separator(app)?, 1)?;
+
+    let _ = app.notification()
+        .builder()
+        .title("Mac Dash")
+        .body(format!("Mac Dash {} is available", version))
+        .show();
+    Ok(())
+}
+
+fn tooltip(battery: Option<&power::BatteryInfo>) -> String {
+    match battery {
+        Some(info) => format!("Mac Dash — {}", power::battery_summary(info)),
+        None => "Mac Dash".into(),
+    }
+}